@@ -0,0 +1,208 @@
+//! Rectangular maps, as most puzzles have one.
+
+use std::ops::{Index, IndexMut};
+
+/// A position as `(row, column)`.
+/// Signed, so that steps off the map can be described and rejected by [`Bounds::contains`].
+pub type Position = (isize, isize);
+
+/// The steps to the neighbours up, right, down and left
+pub const STEPS: [Position; 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// A rectangle of positions, `start` included and `end` excluded
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bounds {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Bounds {
+    /// The rectangle with `width` columns and `height` rows starting at `(0, 0)`
+    pub fn new(width: isize, height: isize) -> Self {
+        Bounds {
+            start: (0, 0),
+            end: (height, width),
+        }
+    }
+
+    pub fn contains(&self, (row, col): Position) -> bool {
+        (self.start.0..self.end.0).contains(&row) && (self.start.1..self.end.1).contains(&col)
+    }
+
+    /// The rectangle covered by both, which may be empty
+    pub fn intersect(&self, other: &Bounds) -> Bounds {
+        Bounds {
+            start: (
+                self.start.0.max(other.start.0),
+                self.start.1.max(other.start.1),
+            ),
+            end: (self.end.0.min(other.end.0), self.end.1.min(other.end.1)),
+        }
+    }
+
+    /// All positions of the rectangle, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let Bounds { start, end } = *self;
+        (start.0..end.0).flat_map(move |row| (start.1..end.1).map(move |col| (row, col)))
+    }
+}
+
+/// A rectangular map stored row by row in a single vector.
+/// Indexing with a position outside the map panics, [`Grid::get`] returns `None` instead.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Reads one row per non-empty line, turning every character into a cell.
+    /// Fails if the lines differ in length or `cell` rejects a character by returning `None`.
+    pub fn parse(
+        content: &str,
+        mut cell: impl FnMut(Position, char) -> Option<T>,
+    ) -> Result<Self, String> {
+        let lines: Vec<&str> = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        let width = lines.first().map_or(0, |line| line.chars().count());
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (row, line) in lines.iter().enumerate() {
+            if line.chars().count() != width {
+                return Err(format!("Line {} has a different length", row + 1));
+            }
+            for (col, c) in line.chars().enumerate() {
+                cells.push(
+                    cell((row as isize, col as isize), c).ok_or_else(|| {
+                        format!("Unexpected character {:?} in line {}", c, row + 1)
+                    })?,
+                );
+            }
+        }
+
+        Ok(Grid {
+            width,
+            height: lines.len(),
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        Bounds::new(self.width as isize, self.height as isize)
+    }
+
+    /// The cells row by row
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    fn index(&self, position: Position) -> Option<usize> {
+        let (row, col) = position;
+        self.bounds()
+            .contains(position)
+            .then(|| row as usize * self.width + col as usize)
+    }
+
+    /// The cell at the position, or `None` if it is outside the map
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.index(position).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.index(position).map(|index| &mut self.cells[index])
+    }
+
+    /// All positions of the map, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        self.bounds().positions()
+    }
+
+    /// The positions up, right, down and left of the position that are on the map
+    pub fn neighbours(&self, (row, col): Position) -> impl Iterator<Item = Position> {
+        let bounds = self.bounds();
+        STEPS
+            .into_iter()
+            .map(move |(d_row, d_col)| (row + d_row, col + d_col))
+            .filter(move |neighbour| bounds.contains(*neighbour))
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", position))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds() {
+        let bounds = Bounds::new(3, 2);
+        assert!(bounds.contains((1, 2)));
+        assert!(!bounds.contains((2, 0)));
+        assert!(!bounds.contains((0, -1)));
+
+        let other = Bounds {
+            start: (1, 1),
+            end: (5, 5),
+        };
+        assert_eq!(
+            bounds.intersect(&other),
+            Bounds {
+                start: (1, 1),
+                end: (2, 3)
+            }
+        );
+        assert_eq!(bounds.intersect(&other).positions().count(), 2);
+    }
+
+    #[test]
+    fn parse_and_neighbours() {
+        let grid = Grid::parse("12\n34\n56\n", |_, c| c.to_digit(10)).unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 3));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(
+            grid.neighbours((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0)]
+        );
+
+        assert!(Grid::parse("12\n3\n", |_, c| c.to_digit(10)).is_err());
+        assert!(Grid::parse("1x\n", |_, c| c.to_digit(10)).is_err());
+    }
+}
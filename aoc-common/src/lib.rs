@@ -0,0 +1,444 @@
+//! Command line flags shared by the days, plus the [`grid`] and [`search`] helpers that more than one puzzle needs.
+//!
+//! A day embeds [`CommonArgs`] with `#[command(flatten)]` and only declares its puzzle-specific flags:
+//!
+//! ```ignore
+//! #[derive(Parser, Debug)]
+//! #[command(version, about, long_about = None)]
+//! struct Args {
+//!     #[command(flatten)]
+//!     common: CommonArgs,
+//! }
+//!
+//! let args = Args::parse();
+//! let content = args.common.read_input(EXAMPLE)?;
+//! let input = parse(&content)?;
+//! args.common.solve(5, |part| match part {
+//!     Part::One => Ok(part_one(&input)),
+//!     Part::Two => Err(Part::Two.unsolved()),
+//! })
+//! ```
+//!
+//! Everything between reading the input and solving is timed as parsing,
+//! so `--time` and `--output kv` report it next to the time of each part.
+//!
+pub mod grid;
+pub mod search;
+
+use clap::{Args, ValueEnum};
+use std::cell::Cell;
+use std::error::Error;
+use std::fmt::Display;
+use std::fs::OpenOptions;
+use std::io::{IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+#[derive(Args, Debug)]
+pub struct CommonArgs {
+    /// The puzzle input, or `-` to read it from stdin
+    #[clap(short, long, required_unless_present = "demo")]
+    pub file_name: Option<PathBuf>,
+
+    /// Use the example from the puzzle description instead of a file
+    #[clap(long, conflicts_with = "file_name")]
+    pub demo: bool,
+
+    /// Which part of the puzzle to solve
+    #[clap(short, long, value_enum, default_value = "1")]
+    pub part: Part,
+
+    /// Solve both parts
+    #[clap(long, conflicts_with = "part")]
+    pub both: bool,
+
+    /// How the answer is printed
+    #[clap(long, value_enum, default_value = "plain")]
+    pub output: Output,
+
+    /// Only print the answer
+    #[clap(short, long)]
+    pub quiet: bool,
+
+    /// Print how long parsing and solving took
+    #[clap(short, long)]
+    pub time: bool,
+
+    /// Warn if parsing and solving together take longer than this many milliseconds
+    #[clap(long, default_value = "1000")]
+    pub slow_threshold: u64,
+
+    /// Also write the answer to this file, as a `key=value` line with --output kv
+    #[clap(short, long)]
+    pub out: Option<PathBuf>,
+
+    /// Append to the --out file instead of overwriting it
+    #[clap(long, requires = "out")]
+    pub append: bool,
+
+    /// Copy the answer to the clipboard
+    #[clap(long)]
+    pub copy: bool,
+
+    /// Group the digits of the answer (ignored with --quiet and --output kv)
+    #[clap(long)]
+    pub pretty_numbers: bool,
+
+    /// Digit separator used by --pretty-numbers
+    #[clap(long, value_enum, default_value = "en")]
+    pub locale: Locale,
+
+    /// Highlight the answer
+    #[clap(long, value_enum, default_value = "auto")]
+    pub color: Color,
+
+    /// Number of threads for days that solve in parallel [default: all cores]
+    #[clap(short, long)]
+    pub jobs: Option<usize>,
+
+    /// When the input was read, so everything until solving counts as parsing
+    #[clap(skip)]
+    read_at: Cell<Option<Instant>>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+}
+
+impl Part {
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+
+    /// The error for a part the day cannot solve yet
+    pub fn unsolved(self) -> Box<dyn Error> {
+        format!("Part {} is not solved yet", self.number()).into()
+    }
+}
+
+/// How the answer is printed
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Output {
+    /// The answer, prefixed with the part if both are solved
+    Plain,
+    /// A single `key=value` line per part with the answer and the times
+    Kv,
+}
+
+/// How the digits are grouped by `--pretty-numbers`
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Locale {
+    /// `1_234_567`
+    Rust,
+    /// `1,234,567`
+    En,
+    /// `1.234.567`
+    De,
+}
+
+impl Locale {
+    fn separator(self) -> char {
+        match self {
+            Locale::Rust => '_',
+            Locale::En => ',',
+            Locale::De => '.',
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Color {
+    /// Highlight if stdout is a terminal and `NO_COLOR` is not set
+    Auto,
+    Always,
+    Never,
+}
+
+impl CommonArgs {
+    /// Prints where the input comes from, unless quiet, and reads it.
+    /// With `--demo` the given example is returned instead.
+    /// The time from here until [`CommonArgs::solve`] is reported as parsing.
+    pub fn read_input(&self, example: &str) -> std::io::Result<String> {
+        let content = self.read_content(example);
+        self.read_at.set(Some(Instant::now()));
+        content
+    }
+
+    fn read_content(&self, example: &str) -> std::io::Result<String> {
+        let banner = self.verbose();
+        match &self.file_name {
+            None => {
+                if banner {
+                    println!("Using the built-in example.");
+                }
+                Ok(example.to_string())
+            }
+            Some(path) if path.as_os_str() == "-" => {
+                if banner {
+                    println!("Reading from stdin.");
+                }
+                let mut content = String::new();
+                std::io::stdin().read_to_string(&mut content)?;
+                Ok(content)
+            }
+            Some(path) => {
+                if banner {
+                    println!("Reading file {}.", path.display());
+                }
+                std::fs::read_to_string(path)
+            }
+        }
+    }
+
+    /// Whether more than the answer may be printed, i.e. neither `--quiet` nor `--output kv` is given
+    pub fn verbose(&self) -> bool {
+        !self.quiet && self.output == Output::Plain
+    }
+
+    /// The parts selected by `--part` and `--both`
+    pub fn parts(&self) -> Vec<Part> {
+        if self.both {
+            vec![Part::One, Part::Two]
+        } else {
+            vec![self.part]
+        }
+    }
+
+    /// Calls the solver for every selected part and prints its answer in the chosen format.
+    /// Afterwards the answers are copied and written to the `--out` file, if asked for.
+    /// With `--output kv` the file gets the same lines as stdout, otherwise just the answers.
+    pub fn solve<T: Display>(
+        &self,
+        day: u8,
+        mut solver: impl FnMut(Part) -> Result<T, Box<dyn Error>>,
+    ) -> Result<(), Box<dyn Error>> {
+        let parse_time = self.read_at.get().map_or(Duration::ZERO, |at| at.elapsed());
+        let mut total = parse_time;
+        let mut answers = Vec::new();
+        let mut lines = Vec::new();
+        for part in self.parts() {
+            let start = Instant::now();
+            let answer = solver(part)?;
+            let solve_time = start.elapsed();
+            total += solve_time;
+            let times = Times {
+                parse: parse_time,
+                solve: solve_time,
+            };
+            let line = self.format(day, part, &answer, times);
+            println!("{}", line);
+            if self.time && self.verbose() {
+                println!(
+                    "Parsed in {}, solved in {}",
+                    humanize(parse_time),
+                    humanize(solve_time)
+                );
+            }
+            let answer = answer.to_string();
+            lines.push(match self.output {
+                Output::Plain => answer.clone(),
+                Output::Kv => line,
+            });
+            answers.push(answer);
+        }
+
+        let threshold = Duration::from_millis(self.slow_threshold);
+        if total > threshold {
+            eprintln!(
+                "Warning: took {}, which is more than the --slow-threshold of {}. \
+                 Days that solve in parallel may be faster with more --jobs.",
+                humanize(total),
+                humanize(threshold)
+            );
+        }
+        if self.copy {
+            copy_to_clipboard(&answers.join("\n"));
+        }
+        if let Some(out) = &self.out {
+            write_answer(out, &lines.join("\n"), self.append)?;
+        }
+        Ok(())
+    }
+
+    fn format(&self, day: u8, part: Part, answer: &impl Display, times: Times) -> String {
+        match self.output {
+            Output::Kv => format!(
+                "day={} part={} answer={} parse_us={} solve_us={}",
+                day,
+                part.number(),
+                answer,
+                times.parse.as_micros(),
+                times.solve.as_micros()
+            ),
+            Output::Plain => {
+                let answer = answer.to_string();
+                let answer = if self.pretty_numbers && !self.quiet {
+                    pretty_number(&answer, self.locale)
+                } else {
+                    answer
+                };
+                let answer = if self.use_color() {
+                    format!("\x1b[1;32m{}\x1b[0m", answer)
+                } else {
+                    answer
+                };
+                if self.both && !self.quiet {
+                    format!("Part {}: {}", part.number(), answer)
+                } else {
+                    answer
+                }
+            }
+        }
+    }
+
+    fn use_color(&self) -> bool {
+        match self.color {
+            Color::Always => true,
+            Color::Never => false,
+            Color::Auto => {
+                std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+            }
+        }
+    }
+}
+
+/// How long parsing and solving a part took
+#[derive(Clone, Copy, Debug)]
+struct Times {
+    parse: Duration,
+    solve: Duration,
+}
+
+/// Formats a duration with a unit that fits its size, e.g. `340 µs` or `1.42 s`
+pub fn humanize(duration: Duration) -> String {
+    let micros = duration.as_micros();
+    if micros < 1_000 {
+        format!("{} µs", micros)
+    } else if micros < 1_000_000 {
+        format!("{:.2} ms", micros as f64 / 1_000.0)
+    } else {
+        format!("{:.2} s", duration.as_secs_f64())
+    }
+}
+
+/// Groups the digits of an integer in threes, e.g. `1234567` becomes `1,234,567`.
+/// Anything that is not an integer is returned unchanged.
+pub fn pretty_number(number: &str, locale: Locale) -> String {
+    let (sign, digits) = match number.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", number),
+    };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return number.to_string();
+    }
+    let mut result = String::from(sign);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            result.push(locale.separator());
+        }
+        result.push(c);
+    }
+    result
+}
+
+/// Writes the answer as a line to the file, either replacing or appending to its content
+pub fn write_answer(path: &Path, answer: &str, append: bool) -> std::io::Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(path)?;
+    writeln!(file, "{}", answer)
+}
+
+/// Places the text on the system clipboard, only reporting failures
+pub fn copy_to_clipboard(text: &str) {
+    if let Err(e) = arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text)) {
+        eprintln!("Error copying to clipboard: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser, Debug)]
+    struct TestArgs {
+        #[command(flatten)]
+        common: CommonArgs,
+    }
+
+    fn parse(args: &[&str]) -> Result<CommonArgs, clap::Error> {
+        TestArgs::try_parse_from(std::iter::once("day").chain(args.iter().copied()))
+            .map(|args| args.common)
+    }
+
+    #[test]
+    fn input_or_demo_required() {
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["--demo", "--file-name", "input.txt"]).is_err());
+        assert!(parse(&["--demo"]).is_ok());
+    }
+
+    #[test]
+    fn selected_parts() {
+        assert_eq!(parse(&["--demo"]).unwrap().parts(), [Part::One]);
+        assert_eq!(parse(&["--demo", "-p", "2"]).unwrap().parts(), [Part::Two]);
+        assert_eq!(
+            parse(&["--demo", "--both"]).unwrap().parts(),
+            [Part::One, Part::Two]
+        );
+        assert!(parse(&["--demo", "--both", "--part", "2"]).is_err());
+    }
+
+    #[test]
+    fn formats() {
+        let args = parse(&["--demo", "--both", "--color", "never"]).unwrap();
+        let times = Times {
+            parse: Duration::from_micros(34),
+            solve: Duration::from_micros(12),
+        };
+        assert_eq!(args.format(5, Part::Two, &143, times), "Part 2: 143");
+
+        let args = parse(&["--demo", "--color", "never", "--pretty-numbers"]).unwrap();
+        assert_eq!(args.format(5, Part::One, &1234567, times), "1,234,567");
+
+        let args = parse(&["--demo", "--output", "kv", "--pretty-numbers"]).unwrap();
+        assert_eq!(
+            args.format(5, Part::One, &1234567, times),
+            "day=5 part=1 answer=1234567 parse_us=34 solve_us=12"
+        );
+    }
+
+    #[test]
+    fn verbosity() {
+        assert!(parse(&["--demo"]).unwrap().verbose());
+        assert!(!parse(&["--demo", "--quiet"]).unwrap().verbose());
+        assert!(!parse(&["--demo", "--output", "kv"]).unwrap().verbose());
+    }
+
+    #[test]
+    fn pretty_numbers() {
+        assert_eq!(pretty_number("1234567", Locale::En), "1,234,567");
+        assert_eq!(pretty_number("-1234", Locale::De), "-1.234");
+        assert_eq!(pretty_number("123", Locale::Rust), "123");
+        assert_eq!(pretty_number("4,6,3", Locale::En), "4,6,3");
+        assert_eq!(pretty_number("11.25", Locale::En), "11.25");
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(humanize(Duration::from_micros(340)), "340 µs");
+        assert_eq!(humanize(Duration::from_micros(1_420)), "1.42 ms");
+        assert_eq!(humanize(Duration::from_millis(1_420)), "1.42 s");
+    }
+}
@@ -0,0 +1,66 @@
+//! Searches that more than one puzzle needs.
+
+/// Modifies the items once for every index and returns the first index for which
+/// the modification passes the check, or `None` if no single modification does.
+///
+/// With the `parallel` feature, the indices are checked on the rayon thread pool,
+/// still returning the first successful one.
+#[cfg(not(feature = "parallel"))]
+pub fn first_successful_modification<'a, T, R, M, C>(
+    items: &'a [T],
+    modify: M,
+    check: C,
+) -> Option<usize>
+where
+    M: Fn(&'a [T], usize) -> R,
+    C: Fn(R) -> bool,
+{
+    (0..items.len()).find(|&i| check(modify(items, i)))
+}
+
+#[cfg(feature = "parallel")]
+pub fn first_successful_modification<'a, T, R, M, C>(
+    items: &'a [T],
+    modify: M,
+    check: C,
+) -> Option<usize>
+where
+    T: Sync,
+    M: Fn(&'a [T], usize) -> R + Sync,
+    C: Fn(R) -> bool + Sync,
+{
+    use rayon::prelude::*;
+    (0..items.len())
+        .into_par_iter()
+        .find_first(|&i| check(modify(items, i)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_index_that_passes() {
+        let without = |items: &[i32], skip: usize| -> Vec<i32> {
+            items
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != skip)
+                .map(|(_, item)| *item)
+                .collect()
+        };
+        let sorted = |items: Vec<i32>| items.windows(2).all(|w| w[0] <= w[1]);
+        assert_eq!(
+            first_successful_modification(&[1, 5, 2, 3], without, sorted),
+            Some(1)
+        );
+        assert_eq!(
+            first_successful_modification(&[1, 2, 3], without, sorted),
+            Some(0)
+        );
+        assert_eq!(
+            first_successful_modification(&[3, 2, 1], without, sorted),
+            None
+        );
+    }
+}
@@ -1,52 +1,82 @@
 //!
-//! Usage `day1 --input-file <file> [--calc-diff]`
+//! Usage `day1 (--file-name <file> | --demo) [--part 1|2 | --both | --calc-diff] [--sim-mode classic|unique|weighted] [--explain]` and the flags shared by all days
 //!
-//! If the `--calc-diff` flag is given, then the difference score will be calculated (task 1)
-//! If not, then the similarity score will be calculated (task 2)
+//! Part 1 (or the `--calc-diff` flag) calculates the difference score (task 1).
+//! Part 2, the default, calculates the similarity score (task 2).
+//! The `--sim-mode` decides how values that appear more than once on the left are counted:
+//! `classic` counts every occurrence (task 2), `unique` counts every value once,
+//! and `weighted` counts every occurrence as often as the value appears on the left.
+//! If the `--explain` flag is given, every step of the calculation is printed before the answer.
+//! This is meant for the small example inputs.
+//! `--input-file` and `-i` still work in place of `--file-name`.
+//!
+//! If `--demo` is given instead of an input file, the example from the puzzle description is used.
 //!
 //! The input file needs to consist of two columns of numbers separated by three spaces.
 //! This is the format of the file in the advent of code.
 //!
-use std::fs;
-use std::path::PathBuf;
-use clap::Parser;
+use aoc_common::{CommonArgs, Part};
+use clap::{Parser, ValueEnum};
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
+#[command(mut_arg("file_name", |arg| arg.alias("input-file").short_alias('i')))]
+#[command(mut_arg("part", |arg| arg.default_value("2")))]
 struct Args {
-    #[arg(short, long)]
-    input_file: PathBuf,
+    #[command(flatten)]
+    common: CommonArgs,
 
-    #[clap(long, short, action)]
+    /// Calculate the difference score, the same as `--part 1`
+    #[clap(long, short, action, conflicts_with_all = ["part", "both"])]
     calc_diff: bool,
-}
 
+    /// How duplicates on the left count towards the similarity score
+    #[clap(long, value_enum, default_value = "classic")]
+    sim_mode: SimMode,
+
+    /// Print every step of the calculation
+    #[clap(long, action, conflicts_with_all = ["quiet", "output"])]
+    explain: bool,
+}
 
 // Change these types according to the numbers in the input
 type NumType = i32;
 type ResultType = u32;
 
-fn main() {
-    let args = Args::parse();
-
-    println!("Reading from {:#?}", &args.input_file.display());
-    println!("Calculating {}", if args.calc_diff { "diff" } else { "similarity" });
-
-    match fs::read_to_string(&args.input_file) {
-        Ok(content) => {
-            let (left_list, right_list) = create_lists(content);
+/// The example from the puzzle description, used by `--demo`
+const EXAMPLE: &str = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = Args::parse();
+    if args.calc_diff {
+        args.common.part = Part::One;
+    }
 
-            if args.calc_diff {
-                println!("{}", calc_diff_score(&left_list, &right_list));
-            } else {
-                println!("{}", calc_sim_score(&left_list, &right_list));
+    let content = args.common.read_input(EXAMPLE)?;
+    let (left_list, right_list) = create_lists(content)?;
+
+    args.common.solve(1, |part| {
+        if args.explain {
+            let lines = match part {
+                Part::One => explain_diff_score(&left_list, &right_list),
+                Part::Two => explain_sim_score(&left_list, &right_list, args.sim_mode),
+            };
+            for line in lines {
+                println!("{}", line);
             }
-
         }
-        Err(e) => {
-            eprintln!("Error reading file {}: {}", &args.input_file.display(), e);
-        }
-    }
+
+        Ok(match part {
+            Part::One => calc_diff_score(&left_list, &right_list),
+            Part::Two => calc_sim_score(&left_list, &right_list, args.sim_mode),
+        })
+    })
 }
 
 /// inserts a value into a sorted vec at a correct place
@@ -55,22 +85,28 @@ fn insert<T: Ord>(vec: &mut Vec<T>, elem: T) {
     vec.insert(pos, elem);
 }
 
-/// Creates a list of two columns from a string
-fn create_lists(content : String) -> (Vec<NumType>, Vec<NumType>) {
+/// Creates a list of two columns from a string.
+/// Fails with the line number if a line is not two numbers separated by three spaces.
+fn create_lists(content : String) -> Result<(Vec<NumType>, Vec<NumType>), String> {
     let mut left_list: Vec<NumType> = Vec::new();
     let mut right_list: Vec<NumType> = Vec::new();
 
-    for line in content.lines() {
+    for (i, line) in content.lines().enumerate() {
         if line.trim().is_empty() {continue}
-        let (l, r) = line.split_once("   ").unwrap();
+        let (l, r) = line
+            .split_once("   ")
+            .ok_or_else(|| format!("line {}: expected two numbers separated by three spaces, got {:?}", i + 1, line))?;
 
         for (num, vec) in [(l, &mut left_list), (r, &mut right_list)] {
-            let elem = num.parse().unwrap();
+            let elem = num
+                .trim()
+                .parse()
+                .map_err(|e| format!("line {}: invalid number {:?}: {}", i + 1, num, e))?;
             insert(vec, elem);
         }
     }
 
-    (left_list, right_list)
+    Ok((left_list, right_list))
 }
 
 /// Calculates the difference according to this rule:
@@ -80,22 +116,120 @@ fn create_lists(content : String) -> (Vec<NumType>, Vec<NumType>) {
 ///
 /// If both slices are sorted, then this does exactly what task 1 of day 1 wants
 fn calc_diff_score(left_list: &[NumType], right_list: &[NumType]) -> ResultType {
-    left_list.iter().zip(right_list.iter()).map(|(left, right)| (left - right).abs() as ResultType).sum()
+    left_list.iter().zip(right_list.iter()).map(|(left, right)| (left - right).unsigned_abs() as ResultType).sum()
+}
+
+/// Describes each pair that [`calc_diff_score`] looks at with its difference, one line per pair
+fn explain_diff_score(left_list: &[NumType], right_list: &[NumType]) -> Vec<String> {
+    left_list
+        .iter()
+        .zip(right_list.iter())
+        .map(|(left, right)| format!("{} {} -> {}", left, right, (left - right).unsigned_abs()))
+        .collect()
+}
+
+/// How the similarity score treats values that appear more than once
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum SimMode {
+    /// Every left element times its count on the right (task 2)
+    Classic,
+    /// Every distinct left value once, times its count on the right
+    Unique,
+    /// Every left element times its count on the left and on the right
+    Weighted,
+}
+
+/// Counts how many times the value appears in the sorted slice.
+/// This does binary search to find the first and the last element.
+/// The difference in indices will be the count.
+fn count(list: &[NumType], value: NumType) -> usize {
+    let start = list.partition_point(|x| *x < value);
+    let end = list.partition_point(|x| *x <= value);
+    end - start
+}
+
+/// The distinct values of the sorted slice, together with their counts
+fn count_runs(list: &[NumType]) -> impl Iterator<Item = (NumType, usize)> + '_ {
+    list.chunk_by(|a, b| a == b).map(|run| (run[0], run.len()))
+}
+
+/// The part of the similarity score contributed by all left elements with this value
+fn sim_term(value: NumType, left_count: usize, right_count: usize, mode: SimMode) -> ResultType {
+    let factor = match mode {
+        SimMode::Classic => left_count,
+        SimMode::Unique => 1,
+        SimMode::Weighted => left_count * left_count,
+    };
+    (factor * right_count) as ResultType * value as ResultType
 }
 
 /// Calculates the similarity score in this way:
 /// Multiply the elements from the left slice with how many times they appear in the right slice.
-/// The slices need to be sorted, as this does binary search to find the first and the last element.
-/// The difference in indices will be the count.
-fn calc_sim_score(left_list: &[NumType], right_list: &[NumType]) -> ResultType {
-    let mut result: ResultType = 0;
-    for left in left_list {
-        let start = right_list.partition_point(|x| x < left);
-        let end = right_list.partition_point(|x| x <= left);
-        let count = end - start;
-
-        result += (count as ResultType) * (*left as ResultType);
+/// How duplicates on the left count is decided by the mode, see [`SimMode`].
+/// The slices need to be sorted, so equal values are next to each other and can be counted by binary search.
+fn calc_sim_score(left_list: &[NumType], right_list: &[NumType], mode: SimMode) -> ResultType {
+    count_runs(left_list)
+        .map(|(value, left_count)| sim_term(value, left_count, count(right_list, value), mode))
+        .sum()
+}
+
+/// Describes each distinct left value that [`calc_sim_score`] looks at with its counts and score, one line per value
+fn explain_sim_score(left_list: &[NumType], right_list: &[NumType], mode: SimMode) -> Vec<String> {
+    count_runs(left_list)
+        .map(|(value, left_count)| {
+            let right_count = count(right_list, value);
+            format!(
+                "{} appears {} times left and {} times right -> {}",
+                value,
+                left_count,
+                right_count,
+                sim_term(value, left_count, right_count, mode)
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_diff_score() {
+        let (left_list, right_list) = create_lists(EXAMPLE.to_string()).unwrap();
+        assert_eq!(calc_diff_score(&left_list, &right_list), 11);
     }
 
-    result
+    #[test]
+    fn example_sim_score() {
+        let (left_list, right_list) = create_lists(EXAMPLE.to_string()).unwrap();
+        assert_eq!(calc_sim_score(&left_list, &right_list, SimMode::Classic), 31);
+    }
+
+    #[test]
+    fn example_sim_score_modes() {
+        let (left_list, right_list) = create_lists(EXAMPLE.to_string()).unwrap();
+        assert_eq!(calc_sim_score(&left_list, &right_list, SimMode::Unique), 13);
+        assert_eq!(calc_sim_score(&left_list, &right_list, SimMode::Weighted), 85);
+    }
+
+    #[test]
+    fn malformed_lists() {
+        assert_eq!(
+            create_lists("3   4\n4,3\n".to_string()),
+            Err("line 2: expected two numbers separated by three spaces, got \"4,3\"".to_string())
+        );
+        assert!(create_lists("3   x\n".to_string()).is_err());
+    }
+
+    #[test]
+    fn example_explain_diff_score() {
+        let (left_list, right_list) = create_lists(EXAMPLE.to_string()).unwrap();
+        insta::assert_snapshot!(explain_diff_score(&left_list, &right_list).join("\n"));
+    }
+
+    #[test]
+    fn example_explain_sim_score() {
+        let (left_list, right_list) = create_lists(EXAMPLE.to_string()).unwrap();
+        insta::assert_snapshot!(explain_sim_score(&left_list, &right_list, SimMode::Classic).join("\n"));
+    }
 }
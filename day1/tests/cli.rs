@@ -0,0 +1,97 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+fn day1() -> Command {
+    Command::cargo_bin("day1").unwrap()
+}
+
+#[test]
+fn quiet_diff_score() {
+    day1()
+        .args(["--input-file", "tests/example.txt", "--calc-diff", "--quiet"])
+        .assert()
+        .success()
+        .stdout("11\n");
+}
+
+#[test]
+fn quiet_sim_score() {
+    day1()
+        .args(["--input-file", "tests/example.txt", "--quiet"])
+        .assert()
+        .success()
+        .stdout("31\n");
+}
+
+#[test]
+fn kv_output() {
+    day1()
+        .args(["--input-file", "tests/example.txt", "--output", "kv"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("day=1 part=2 answer=31 parse_us="));
+}
+
+#[test]
+fn kv_output_to_file() {
+    let out = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("day1_kv.txt");
+    day1()
+        .args(["--input-file", "tests/example.txt", "--output", "kv", "--out"])
+        .arg(&out)
+        .assert()
+        .success();
+    let written = std::fs::read_to_string(&out).unwrap();
+    assert!(written.starts_with("day=1 part=2 answer=31 parse_us="));
+    assert_eq!(written.lines().count(), 1);
+}
+
+#[test]
+fn explain_conflicts_with_kv_output() {
+    day1()
+        .args(["--input-file", "tests/example.txt", "--output", "kv", "--explain"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn both_parts_from_stdin() {
+    day1()
+        .args(["--file-name", "-", "--both", "--quiet"])
+        .write_stdin(std::fs::read_to_string("tests/example.txt").unwrap())
+        .assert()
+        .success()
+        .stdout("11\n31\n");
+}
+
+#[test]
+fn demo_without_input_file() {
+    day1()
+        .args(["--demo", "--calc-diff", "--quiet"])
+        .assert()
+        .success()
+        .stdout("11\n");
+}
+
+#[test]
+fn input_file_or_demo_required() {
+    day1().assert().failure();
+}
+
+#[test]
+fn missing_file() {
+    day1()
+        .args(["--input-file", "tests/does_not_exist.txt"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::starts_with("Error:"));
+}
+
+#[test]
+fn malformed_input() {
+    day1()
+        .args(["--input-file", "tests/malformed.txt", "--quiet"])
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(predicate::str::contains("line 2: expected two numbers separated by three spaces"));
+}
@@ -0,0 +1,131 @@
+//! Day 10:
+//! The input is a topographic map with heights from 0 to 9.
+//! A hiking trail starts at height 0, ends at height 9 and always goes up by exactly 1
+//! with each step up, down, left or right.
+//! The score of a trailhead (a position with height 0) is the number of 9s reachable from it.
+//! The answer is the sum of the scores of all trailheads.
+//!
+//! Part 2: The rating of a trailhead is the number of distinct hiking trails starting there.
+//! The answer is the sum of the ratings of all trailheads.
+//!
+//! `$ day10 --file-name <file> [--part 2]` to execute.
+//! `$ day10 --demo` runs on the example from the puzzle description instead.
+//!
+use aoc_common::grid::{Grid, Position};
+use aoc_common::{CommonArgs, Part};
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
+}
+
+type Height = u8;
+type ResultType = usize;
+
+/// The example from the puzzle description, used by `--demo`
+const EXAMPLE: &str = "89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let content = args.common.read_input(EXAMPLE)?;
+    let map = Map::parse(&content)?;
+
+    args.common.solve(10, |part| match part {
+        Part::One => Ok(sum_trailheads(&map, Measure::Score)),
+        Part::Two => Ok(sum_trailheads(&map, Measure::Rating)),
+    })
+}
+
+/// The heights of the map
+struct Map {
+    heights: Grid<Height>,
+}
+
+impl Map {
+    fn parse(content: &str) -> Result<Self, String> {
+        let heights = Grid::parse(content, |_, c| {
+            c.to_digit(10).map(|height| height as Height)
+        })?;
+        Ok(Map { heights })
+    }
+
+    /// The neighbours that are exactly one higher than the position
+    fn uphill(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        let next = self.heights[position] + 1;
+        self.heights
+            .neighbours(position)
+            .filter(move |neighbour| self.heights[*neighbour] == next)
+    }
+
+    fn trailheads(&self) -> impl Iterator<Item = Position> + '_ {
+        self.heights
+            .positions()
+            .filter(|position| self.heights[*position] == 0)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Measure {
+    /// The number of distinct 9s reachable
+    Score,
+    /// The number of distinct trails to any 9
+    Rating,
+}
+
+/// Walks every trail from the trailhead by a depth-first search and counts the 9s reached.
+/// For the score, every position is only entered once. As heights strictly increase along a trail,
+/// entering positions again instead reaches every 9 once per trail, which is the rating.
+fn evaluate(map: &Map, trailhead: Position, measure: Measure) -> ResultType {
+    let mut visited = Grid::new(map.heights.width(), map.heights.height(), false);
+    let mut stack = vec![trailhead];
+    let mut peaks = 0;
+    while let Some(position) = stack.pop() {
+        if measure == Measure::Score && std::mem::replace(&mut visited[position], true) {
+            continue;
+        }
+        if map.heights[position] == 9 {
+            peaks += 1;
+        }
+        stack.extend(map.uphill(position));
+    }
+    peaks
+}
+
+fn sum_trailheads(map: &Map, measure: Measure) -> ResultType {
+    map.trailheads()
+        .map(|trailhead| evaluate(map, trailhead, measure))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_scores() {
+        assert_eq!(
+            sum_trailheads(&Map::parse(EXAMPLE).unwrap(), Measure::Score),
+            36
+        );
+    }
+
+    #[test]
+    fn example_ratings() {
+        assert_eq!(
+            sum_trailheads(&Map::parse(EXAMPLE).unwrap(), Measure::Rating),
+            81
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! Day 11:
+//! A line of stones with numbers changes every time you blink.
+//! Every stone changes by the first rule that applies:
+//! - `0` becomes `1`.
+//! - A number with an even count of digits splits into two stones, the left and right half of its digits.
+//! - Any other number is multiplied by 2024.
+//!
+//! The answer is the number of stones after 25 blinks, or after 75 blinks for part 2.
+//!
+//! `$ day11 --file-name <file> [--part 2] [--blinks <n>]` to execute.
+//! `--blinks` replaces the number of blinks of the part.
+//! `$ day11 --demo` runs on the example from the puzzle description instead.
+//!
+//! The order of the stones never matters, so instead of the list of stones,
+//! only how many stones there are of each number is tracked.
+//! Equal stones then only need to be transformed once per blink.
+//!
+use aoc_common::{CommonArgs, Part};
+use clap::Parser;
+use std::collections::HashMap;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// Blink this many times instead of 25 (part 1) or 75 (part 2)
+    #[clap(long)]
+    blinks: Option<usize>,
+}
+
+type Stone = u64;
+type ResultType = u128;
+
+/// How many stones there are of each number
+type Counts = HashMap<Stone, ResultType>;
+
+/// How often to blink in part 1 and part 2
+const BLINKS: usize = 25;
+const MORE_BLINKS: usize = 75;
+
+/// The example from the puzzle description, used by `--demo`
+const EXAMPLE: &str = "125 17";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let content = args.common.read_input(EXAMPLE)?;
+    let stones = content
+        .split_whitespace()
+        .map(str::parse)
+        .collect::<Result<Vec<Stone>, _>>()?;
+
+    args.common.solve(11, |part| match part {
+        Part::One => count_after_blinks(&stones, args.blinks.unwrap_or(BLINKS)),
+        Part::Two => count_after_blinks(&stones, args.blinks.unwrap_or(MORE_BLINKS)),
+    })
+}
+
+/// The stones a single stone turns into after one blink
+fn change(stone: Stone) -> Result<Vec<Stone>, String> {
+    if stone == 0 {
+        return Ok(vec![1]);
+    }
+    let digits = stone.ilog10() + 1;
+    if digits.is_multiple_of(2) {
+        let half = 10u64.pow(digits / 2);
+        Ok(vec![stone / half, stone % half])
+    } else {
+        stone
+            .checked_mul(2024)
+            .map(|stone| vec![stone])
+            .ok_or_else(|| format!("Stone {} times 2024 exceeds u64", stone))
+    }
+}
+
+/// Adds to the count, failing instead of overflowing
+fn add(total: ResultType, count: ResultType) -> Result<ResultType, String> {
+    total
+        .checked_add(count)
+        .ok_or_else(|| "The count exceeds u128".to_string())
+}
+
+fn blink(counts: &Counts) -> Result<Counts, String> {
+    let mut next = Counts::with_capacity(counts.len());
+    for (stone, count) in counts {
+        for new_stone in change(*stone)? {
+            let total = next.entry(new_stone).or_default();
+            *total = add(*total, *count)?;
+        }
+    }
+    Ok(next)
+}
+
+fn count_after_blinks(
+    stones: &[Stone],
+    blinks: usize,
+) -> Result<ResultType, Box<dyn std::error::Error>> {
+    let mut counts = Counts::new();
+    for stone in stones {
+        *counts.entry(*stone).or_default() += 1;
+    }
+    for _ in 0..blinks {
+        counts = blink(&counts)?;
+    }
+    Ok(counts
+        .values()
+        .try_fold(0, |total, count| add(total, *count))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_blinks() {
+        assert_eq!(count_after_blinks(&[125, 17], 6).unwrap(), 22);
+        assert_eq!(count_after_blinks(&[125, 17], BLINKS).unwrap(), 55312);
+        assert_eq!(
+            count_after_blinks(&[125, 17], MORE_BLINKS).unwrap(),
+            65601038650482
+        );
+    }
+
+    #[test]
+    fn counts_beyond_u64() {
+        assert!(count_after_blinks(&[125, 17], 110).unwrap() > u64::MAX as ResultType);
+        assert!(count_after_blinks(&[125, 17], 300).is_err());
+    }
+}
@@ -0,0 +1,341 @@
+//! Day 12:
+//! The garden is a grid of plots, each marked with a letter for the plant growing there.
+//! Plots with the same plant that touch horizontally or vertically form a region.
+//! The price of fencing a region is its area (number of plots) times its perimeter
+//! (number of plot sides that border another region or the edge of the map).
+//! The answer is the total price of all regions.
+//!
+//! Part 2: With the bulk discount, the price is the area times the number of straight sides
+//! of the fence. A polygon has as many sides as corners, so the corners of each region are counted.
+//!
+//! `$ day12 --file-name <file> [--part 2]` to execute.
+//! `$ day12 --demo` runs on the example from the puzzle description instead.
+//! `$ day12 --file-name <file> --region-graph <graph_file> [--graph-format json|dot]` also writes
+//! which regions border which, with the length of the border they share.
+//!
+use aoc_common::grid::{Grid, Position, STEPS};
+use aoc_common::{CommonArgs, Part};
+use clap::{Parser, ValueEnum};
+use std::collections::BTreeMap;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// Writes the regions and the borders between them to this file
+    #[clap(long)]
+    region_graph: Option<PathBuf>,
+
+    /// Format of the --region-graph file
+    #[clap(long, value_enum, default_value_t = GraphFormat::Json, requires = "region_graph")]
+    graph_format: GraphFormat,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum GraphFormat {
+    /// `{"regions": [...], "borders": [...]}`
+    Json,
+    /// An undirected graphviz graph
+    Dot,
+}
+
+type ResultType = usize;
+
+/// The example from the puzzle description, used by `--demo`
+const EXAMPLE: &str = "RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let content = args.common.read_input(EXAMPLE)?;
+    let garden = parse(&content)?;
+    let regions = find_regions(&garden);
+
+    if let Some(path) = &args.region_graph {
+        let borders = region_borders(&garden, &regions);
+        let mut writer = BufWriter::new(std::fs::File::create(path)?);
+        match args.graph_format {
+            GraphFormat::Json => write_json(&garden, &regions, &borders, &mut writer)?,
+            GraphFormat::Dot => write_dot(&regions, &borders, &mut writer)?,
+        }
+        writer.flush()?;
+    }
+
+    args.common.solve(12, |part| match part {
+        Part::One => Ok(total_price(&garden, &regions)),
+        Part::Two => Ok(total_discount_price(&garden, &regions)),
+    })
+}
+
+/// The plants of the garden
+type Garden = Grid<u8>;
+
+fn parse(content: &str) -> Result<Garden, String> {
+    Grid::parse(content, |_, c| c.is_ascii().then_some(c as u8))
+}
+
+/// Plots of the same plant connected horizontally or vertically
+struct Region {
+    plant: u8,
+    plots: Vec<Position>,
+}
+
+impl Region {
+    fn area(&self) -> usize {
+        self.plots.len()
+    }
+
+    /// Counts the sides of the plots that do not touch a plot of the same region
+    fn perimeter(&self, garden: &Garden) -> usize {
+        self.plots
+            .iter()
+            .map(|(row, col)| {
+                STEPS
+                    .iter()
+                    .filter(|(d_row, d_col)| {
+                        garden.get((row + d_row, col + d_col)) != Some(&self.plant)
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Counts the corners of the fence, which equals the number of its sides.
+    /// For every plot and every pair of neighbouring directions, the plot has an outer corner
+    /// if neither neighbour belongs to the region, and an inner corner if both do but the
+    /// diagonal plot between them does not.
+    fn sides(&self, garden: &Garden) -> usize {
+        let same = |(row, col): Position, (d_row, d_col): Position| {
+            garden.get((row + d_row, col + d_col)) == Some(&self.plant)
+        };
+        self.plots
+            .iter()
+            .map(|plot| {
+                (0..STEPS.len())
+                    .filter(|i| {
+                        let first = STEPS[*i];
+                        let second = STEPS[(i + 1) % STEPS.len()];
+                        let diagonal = (first.0 + second.0, first.1 + second.1);
+                        match (same(*plot, first), same(*plot, second)) {
+                            (false, false) => true,
+                            (true, true) => !same(*plot, diagonal),
+                            _ => false,
+                        }
+                    })
+                    .count()
+            })
+            .sum()
+    }
+}
+
+/// Splits the garden into regions by flood-filling from every plot not yet in a region
+fn find_regions(garden: &Garden) -> Vec<Region> {
+    let mut assigned = Grid::new(garden.width(), garden.height(), false);
+    let mut regions = Vec::new();
+    for start in garden.positions() {
+        if std::mem::replace(&mut assigned[start], true) {
+            continue;
+        }
+
+        let plant = garden[start];
+        let mut plots = Vec::new();
+        let mut stack = vec![start];
+        while let Some(plot) = stack.pop() {
+            plots.push(plot);
+            for neighbour in garden.neighbours(plot) {
+                if !assigned[neighbour] && garden[neighbour] == plant {
+                    assigned[neighbour] = true;
+                    stack.push(neighbour);
+                }
+            }
+        }
+        regions.push(Region { plant, plots });
+    }
+    regions
+}
+
+fn total_price(garden: &Garden, regions: &[Region]) -> ResultType {
+    regions
+        .iter()
+        .map(|region| region.area() * region.perimeter(garden))
+        .sum()
+}
+
+/// Pairs of region indices `(lower, higher)` mapped to the number of plot sides they share
+type Borders = BTreeMap<(usize, usize), usize>;
+
+/// Finds the borders between regions by looking at every plot's right and lower neighbour,
+/// so that each shared side is only counted once
+fn region_borders(garden: &Garden, regions: &[Region]) -> Borders {
+    let mut region_of = Grid::new(garden.width(), garden.height(), 0);
+    for (id, region) in regions.iter().enumerate() {
+        for plot in &region.plots {
+            region_of[*plot] = id;
+        }
+    }
+
+    let mut borders = Borders::new();
+    for (id, region) in regions.iter().enumerate() {
+        for (row, col) in &region.plots {
+            for neighbour in [(row + 1, *col), (*row, col + 1)] {
+                if let Some(&other) = region_of.get(neighbour) {
+                    if other != id {
+                        *borders.entry((id.min(other), id.max(other))).or_default() += 1;
+                    }
+                }
+            }
+        }
+    }
+    borders
+}
+
+/// The plant as the inside of a JSON or DOT string, escaping quotes, backslashes and control characters
+fn escape(plant: u8) -> String {
+    match plant {
+        b'"' | b'\\' => format!("\\{}", plant as char),
+        _ if plant.is_ascii_control() => format!("\\u{:04x}", plant),
+        _ => (plant as char).to_string(),
+    }
+}
+
+fn write_json(
+    garden: &Garden,
+    regions: &[Region],
+    borders: &Borders,
+    writer: &mut impl Write,
+) -> std::io::Result<()> {
+    writeln!(writer, "{{")?;
+    writeln!(writer, "  \"regions\": [")?;
+    for (id, region) in regions.iter().enumerate() {
+        let separator = if id + 1 < regions.len() { "," } else { "" };
+        writeln!(
+            writer,
+            "    {{\"id\": {}, \"plant\": \"{}\", \"area\": {}, \"perimeter\": {}}}{}",
+            id,
+            escape(region.plant),
+            region.area(),
+            region.perimeter(garden),
+            separator
+        )?;
+    }
+    writeln!(writer, "  ],")?;
+    writeln!(writer, "  \"borders\": [")?;
+    for (i, ((a, b), length)) in borders.iter().enumerate() {
+        let separator = if i + 1 < borders.len() { "," } else { "" };
+        writeln!(
+            writer,
+            "    {{\"a\": {}, \"b\": {}, \"length\": {}}}{}",
+            a, b, length, separator
+        )?;
+    }
+    writeln!(writer, "  ]")?;
+    writeln!(writer, "}}")
+}
+
+fn write_dot(
+    regions: &[Region],
+    borders: &Borders,
+    writer: &mut impl Write,
+) -> std::io::Result<()> {
+    writeln!(writer, "graph regions {{")?;
+    for (id, region) in regions.iter().enumerate() {
+        writeln!(
+            writer,
+            "  {} [label=\"{} ({})\"];",
+            id,
+            escape(region.plant),
+            region.area()
+        )?;
+    }
+    for ((a, b), length) in borders {
+        writeln!(writer, "  {} -- {} [label=\"{}\"];", a, b, length)?;
+    }
+    writeln!(writer, "}}")
+}
+
+fn total_discount_price(garden: &Garden, regions: &[Region]) -> ResultType {
+    regions
+        .iter()
+        .map(|region| region.area() * region.sides(garden))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_price() {
+        let garden = parse(EXAMPLE).unwrap();
+        assert_eq!(total_price(&garden, &find_regions(&garden)), 1930);
+    }
+
+    #[test]
+    fn example_discount_price() {
+        let garden = parse(EXAMPLE).unwrap();
+        assert_eq!(total_discount_price(&garden, &find_regions(&garden)), 1206);
+
+        let garden = parse("EEEEE\nEXXXX\nEEEEE\nEXXXX\nEEEEE").unwrap();
+        assert_eq!(total_discount_price(&garden, &find_regions(&garden)), 236);
+    }
+
+    #[test]
+    fn region_json() {
+        let garden = parse("AA\"\n\\\\\"").unwrap();
+        let regions = find_regions(&garden);
+        let borders = region_borders(&garden, &regions);
+        let mut json = Vec::new();
+        write_json(&garden, &regions, &borders, &mut json).unwrap();
+        assert_eq!(
+            String::from_utf8(json).unwrap(),
+            r#"{
+  "regions": [
+    {"id": 0, "plant": "A", "area": 2, "perimeter": 6},
+    {"id": 1, "plant": "\"", "area": 2, "perimeter": 6},
+    {"id": 2, "plant": "\\", "area": 2, "perimeter": 6}
+  ],
+  "borders": [
+    {"a": 0, "b": 1, "length": 1},
+    {"a": 0, "b": 2, "length": 2},
+    {"a": 1, "b": 2, "length": 1}
+  ]
+}
+"#
+        );
+    }
+
+    #[test]
+    fn region_graph() {
+        let garden = parse("AAB\nAAB\nCCB").unwrap();
+        let regions = find_regions(&garden);
+        let borders = region_borders(&garden, &regions);
+        let mut dot = Vec::new();
+        write_dot(&regions, &borders, &mut dot).unwrap();
+        assert_eq!(
+            String::from_utf8(dot).unwrap(),
+            "graph regions {
+  0 [label=\"A (4)\"];
+  1 [label=\"B (3)\"];
+  2 [label=\"C (2)\"];
+  0 -- 1 [label=\"2\"];
+  0 -- 2 [label=\"2\"];
+  1 -- 2 [label=\"1\"];
+}
+"
+        );
+    }
+}
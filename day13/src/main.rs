@@ -0,0 +1,210 @@
+//! Day 13:
+//! Every claw machine has two buttons, each moving the claw by a fixed `X` and `Y` step,
+//! and a prize at some position. Pressing A costs 3 tokens, pressing B costs 1 token.
+//! Each button may be pressed at most 100 times.
+//! The answer is the fewest tokens needed to win every prize that can be won at all.
+//!
+//! Part 2: Every prize is 10000000000000 further away in both `X` and `Y`,
+//! and the buttons may be pressed any number of times.
+//!
+//! `$ day13 --file-name <file> [--part 2]` to execute.
+//! `$ day13 --demo` runs on the example from the puzzle description instead.
+//!
+//! Winning a prize means solving `a * A + b * B = Prize` for whole, non-negative `a` and `b`.
+//! That is a system of two linear equations, which has at most one solution
+//! unless the buttons move in the same direction. It is solved directly with Cramer's rule
+//! instead of trying out button presses.
+//! The products are computed in `i128` with checked arithmetic, so the part 2 offsets cannot overflow,
+//! and the divisions only accept exact results; no floating point is involved.
+//!
+use aoc_common::{CommonArgs, Part};
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
+}
+
+type NumType = i64;
+type ResultType = i64;
+
+const COST_A: NumType = 3;
+const COST_B: NumType = 1;
+const MAX_PRESSES: NumType = 100;
+
+/// How much further away the prizes are in part 2
+const PRIZE_OFFSET: NumType = 10_000_000_000_000;
+
+/// The example from the puzzle description, used by `--demo`
+const EXAMPLE: &str = "Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let content = args.common.read_input(EXAMPLE)?;
+    let machines = parse(&content)?;
+
+    args.common.solve(13, |part| match part {
+        Part::One => Ok(total_cost(&machines, Some(MAX_PRESSES))?),
+        Part::Two => {
+            let machines = machines
+                .iter()
+                .map(|machine| machine.moved_prize(PRIZE_OFFSET))
+                .collect::<Option<Vec<_>>>()
+                .ok_or("A prize is too far away")?;
+            Ok(total_cost(&machines, None)?)
+        }
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Machine {
+    a: (NumType, NumType),
+    b: (NumType, NumType),
+    prize: (NumType, NumType),
+}
+
+impl Machine {
+    /// The number of presses of A and B that win the prize, if there is a whole, non-negative one.
+    /// If both buttons move in the same direction, there is no single solution and `None` is returned.
+    /// `None` is also returned if the presses do not fit into [`NumType`].
+    fn presses(&self) -> Option<(NumType, NumType)> {
+        let wide = |(x, y): (NumType, NumType)| (i128::from(x), i128::from(y));
+        let ((ax, ay), (bx, by), (px, py)) = (wide(self.a), wide(self.b), wide(self.prize));
+        let cross = |x1: i128, y1: i128, x2: i128, y2: i128| {
+            x1.checked_mul(y2)?.checked_sub(y1.checked_mul(x2)?)
+        };
+        let determinant = cross(ax, ay, bx, by)?;
+        if determinant == 0 {
+            return None;
+        }
+        let a = cross(px, py, bx, by)?;
+        let b = cross(ax, ay, px, py)?;
+        if a % determinant != 0 || b % determinant != 0 {
+            return None;
+        }
+        let (a, b) = (a / determinant, b / determinant);
+        if a < 0 || b < 0 {
+            return None;
+        }
+        Some((NumType::try_from(a).ok()?, NumType::try_from(b).ok()?))
+    }
+
+    /// The same machine with the prize moved by the offset in both directions,
+    /// or `None` if the prize would overflow
+    fn moved_prize(&self, offset: NumType) -> Option<Self> {
+        Some(Machine {
+            prize: (
+                self.prize.0.checked_add(offset)?,
+                self.prize.1.checked_add(offset)?,
+            ),
+            ..*self
+        })
+    }
+}
+
+/// Collects all numbers in the line, including their sign, ignoring everything else
+fn numbers(line: &str) -> Vec<NumType> {
+    line.split(|c: char| !c.is_ascii_digit() && c != '-')
+        .filter(|part| !part.is_empty())
+        .filter_map(|part| part.parse().ok())
+        .collect()
+}
+
+/// Reads the machines, three non-empty lines each: button A, button B and the prize
+fn parse(content: &str) -> Result<Vec<Machine>, String> {
+    let lines: Vec<&str> = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    lines
+        .chunks(3)
+        .map(|chunk| {
+            let pair = |line: Option<&&str>| match line.map(|line| numbers(line))?[..] {
+                [x, y] => Some((x, y)),
+                _ => None,
+            };
+            let invalid = || format!("Invalid machine: {:?}", chunk);
+            Ok(Machine {
+                a: pair(chunk.first()).ok_or_else(invalid)?,
+                b: pair(chunk.get(1)).ok_or_else(invalid)?,
+                prize: pair(chunk.get(2)).ok_or_else(invalid)?,
+            })
+        })
+        .collect()
+}
+
+/// The tokens for all prizes that can be won with at most `max_presses` per button, if given.
+/// Fails if the tokens do not fit into [`ResultType`].
+fn total_cost(machines: &[Machine], max_presses: Option<NumType>) -> Result<ResultType, String> {
+    let allowed = |presses: NumType| max_presses.is_none_or(|max| presses <= max);
+    machines
+        .iter()
+        .filter_map(Machine::presses)
+        .filter(|(a, b)| allowed(*a) && allowed(*b))
+        .try_fold(0, |total: ResultType, (a, b)| {
+            a.checked_mul(COST_A)
+                .and_then(|cost| cost.checked_add(b.checked_mul(COST_B)?))
+                .and_then(|cost| total.checked_add(cost))
+        })
+        .ok_or_else(|| "Too many tokens to count".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_cost() {
+        assert_eq!(
+            total_cost(&parse(EXAMPLE).unwrap(), Some(MAX_PRESSES)),
+            Ok(480)
+        );
+    }
+
+    #[test]
+    fn example_far_prizes() {
+        let machines: Vec<Machine> = parse(EXAMPLE)
+            .unwrap()
+            .iter()
+            .map(|machine| machine.moved_prize(PRIZE_OFFSET).unwrap())
+            .collect();
+        let winnable: Vec<bool> = machines.iter().map(|m| m.presses().is_some()).collect();
+        assert_eq!(winnable, [false, true, false, true]);
+        assert_eq!(total_cost(&machines, None), Ok(875318608908));
+    }
+
+    #[test]
+    fn negative_numbers() {
+        let machines = parse("Button A: X+2, Y-1\nButton B: X-1, Y+1\nPrize: X=-1, Y=2").unwrap();
+        assert_eq!(machines[0].prize, (-1, 2));
+        assert_eq!(machines[0].presses(), Some((1, 3)));
+    }
+
+    #[test]
+    fn cost_overflow() {
+        let machines = parse(&format!(
+            "Button A: X+1, Y+0\nButton B: X+0, Y+1\nPrize: X={}, Y=0",
+            NumType::MAX
+        ))
+        .unwrap();
+        assert!(total_cost(&machines, None).is_err());
+    }
+}
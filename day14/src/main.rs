@@ -0,0 +1,372 @@
+//! Day 14:
+//! Robots move around an area 101 tiles wide and 103 tiles tall,
+//! or of the size given by `--width` and `--height`.
+//! Every line describes a robot as `p=x,y v=dx,dy`, its position and how far it moves per second.
+//! Robots that leave the area on one side come back in on the opposite side.
+//!
+//! After 100 seconds, the robots in each quadrant are counted; robots exactly in the
+//! middle row or column do not belong to any quadrant. An even width or height has no middle.
+//! The answer is the safety factor, the product of the four counts.
+//!
+//! Part 2: At some point most robots arrange themselves into a picture of a Christmas tree.
+//! The positions repeat after `width * height` seconds, and the picture is taken to be the frame
+//! within that period where the robots are bunched together the most, i.e. with the lowest variance.
+//! The answer is the number of seconds until that frame.
+//! With `--score` another heuristic picks the frame: the highest clustering or safety factor.
+//! With `--frame <file>` the frame is also written to that file as ASCII art for checking it by eye.
+//!
+//! `$ day14 --file-name <file> [--part 2 [--frame <frame_file>]]` to execute.
+//! `$ day14 --demo` runs on the example from the puzzle description instead,
+//! whose area is 11 tiles wide and 7 tiles tall.
+//! `$ day14 --file-name <file> --score variance|clustering` scores the final positions with a different
+//! heuristic instead of the safety factor, to compare heuristics for spotting unusual arrangements.
+//!
+use aoc_common::{CommonArgs, Part};
+use clap::{Parser, ValueEnum};
+use std::collections::HashSet;
+use std::fmt;
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// How the positions of the robots are scored [default: quadrants, or variance for part 2]
+    #[clap(long, value_enum)]
+    score: Option<Score>,
+
+    /// Writes the part 2 frame to this file, `#` for robots and `.` for empty tiles
+    #[clap(long)]
+    frame: Option<PathBuf>,
+
+    /// Width of the area [default: 101, or 11 with --demo]
+    #[clap(long)]
+    width: Option<NumType>,
+
+    /// Height of the area [default: 103, or 7 with --demo]
+    #[clap(long)]
+    height: Option<NumType>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Score {
+    /// The safety factor, the product of the robot counts per quadrant
+    Quadrants,
+    /// The sum of the variances of the x and y coordinates, low when the robots are bunched together
+    Variance,
+    /// The number of occupied tiles with at least one occupied neighbouring tile
+    Clustering,
+}
+
+impl Score {
+    fn evaluate(self, positions: &[(NumType, NumType)], area: &Area) -> Value {
+        match self {
+            Score::Quadrants => Value::Count(safety_factor(positions, area)),
+            Score::Variance => Value::Measure(variance(positions)),
+            Score::Clustering => Value::Count(clustering(positions)),
+        }
+    }
+
+    /// Whether a lower score means the robots are arranged more unusually
+    fn lower_is_better(self) -> bool {
+        self == Score::Variance
+    }
+}
+
+/// An answer, which is only fractional for the heuristics that measure rather than count
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Value {
+    Count(ResultType),
+    Measure(f64),
+}
+
+impl Value {
+    fn as_f64(self) -> f64 {
+        match self {
+            Value::Count(count) => count as f64,
+            Value::Measure(measure) => measure,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Count(count) => write!(f, "{}", count),
+            Value::Measure(measure) => write!(f, "{}", measure),
+        }
+    }
+}
+
+type NumType = i64;
+type ResultType = usize;
+
+const WIDTH: NumType = 101;
+const HEIGHT: NumType = 103;
+const SECONDS: NumType = 100;
+
+/// The example robots move in a smaller area
+const EXAMPLE_WIDTH: NumType = 11;
+const EXAMPLE_HEIGHT: NumType = 7;
+
+/// The example from the puzzle description, used by `--demo`
+const EXAMPLE: &str = "p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let content = args.common.read_input(EXAMPLE)?;
+    let robots = parse(&content)?;
+    let (width, height) = if args.common.demo {
+        (EXAMPLE_WIDTH, EXAMPLE_HEIGHT)
+    } else {
+        (WIDTH, HEIGHT)
+    };
+    let area = Area {
+        width: args.width.unwrap_or(width),
+        height: args.height.unwrap_or(height),
+    };
+    if area.width <= 0 || area.height <= 0 {
+        return Err("The area needs a positive width and height".into());
+    }
+
+    let positions: Vec<_> = robots
+        .iter()
+        .map(|robot| robot.position_after(SECONDS, &area))
+        .collect();
+
+    let mut tree = None;
+    args.common.solve(14, |part| match part {
+        Part::One => Ok(args
+            .score
+            .unwrap_or(Score::Quadrants)
+            .evaluate(&positions, &area)),
+        Part::Two => {
+            let seconds = find_tree(&robots, &area, args.score.unwrap_or(Score::Variance));
+            tree = Some(seconds);
+            Ok(Value::Count(seconds as ResultType))
+        }
+    })?;
+
+    if let (Some(path), Some(seconds)) = (&args.frame, tree) {
+        let positions: Vec<_> = robots
+            .iter()
+            .map(|robot| robot.position_after(seconds, &area))
+            .collect();
+        std::fs::write(path, render(&positions, &area))?;
+    }
+
+    Ok(())
+}
+
+/// The size of the area the robots move in
+struct Area {
+    width: NumType,
+    height: NumType,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Robot {
+    position: (NumType, NumType),
+    velocity: (NumType, NumType),
+}
+
+impl Robot {
+    /// Where the robot is after the seconds, wrapping around the edges of the area
+    fn position_after(&self, seconds: NumType, area: &Area) -> (NumType, NumType) {
+        (
+            (self.position.0 + self.velocity.0 * seconds).rem_euclid(area.width),
+            (self.position.1 + self.velocity.1 * seconds).rem_euclid(area.height),
+        )
+    }
+}
+
+/// Reads `x,y` into a pair
+fn pair(s: &str) -> Result<(NumType, NumType), Box<dyn std::error::Error>> {
+    let (x, y) = s
+        .split_once(',')
+        .ok_or_else(|| format!("Expected x,y, got {}", s))?;
+    Ok((x.trim().parse()?, y.trim().parse()?))
+}
+
+fn parse(content: &str) -> Result<Vec<Robot>, Box<dyn std::error::Error>> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (position, velocity) = line
+                .trim()
+                .strip_prefix("p=")
+                .and_then(|rest| rest.split_once(" v="))
+                .ok_or_else(|| format!("Invalid robot: {}", line))?;
+            Ok(Robot {
+                position: pair(position)?,
+                velocity: pair(velocity)?,
+            })
+        })
+        .collect()
+}
+
+/// Whether the coordinate is in the upper half of the size, or `None` if it is on the middle line.
+/// Only an odd size has a middle line; an even size splits evenly between both halves.
+fn half(coordinate: NumType, size: NumType) -> Option<bool> {
+    if size % 2 == 1 && coordinate == size / 2 {
+        None
+    } else {
+        Some(coordinate >= (size + 1) / 2)
+    }
+}
+
+/// Multiplies the number of robots in each quadrant, ignoring the middle row and column
+fn safety_factor(positions: &[(NumType, NumType)], area: &Area) -> ResultType {
+    let mut quadrants = [0; 4];
+    for (x, y) in positions {
+        let (Some(right), Some(lower)) = (half(*x, area.width), half(*y, area.height)) else {
+            continue;
+        };
+        quadrants[usize::from(right) + 2 * usize::from(lower)] += 1;
+    }
+    quadrants.iter().product()
+}
+
+/// The sum of the variances of both coordinates
+fn variance(positions: &[(NumType, NumType)]) -> f64 {
+    if positions.is_empty() {
+        return 0.0;
+    }
+    let n = positions.len() as f64;
+    let axis_variance = |values: Vec<f64>| {
+        let mean = values.iter().sum::<f64>() / n;
+        values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n
+    };
+    axis_variance(positions.iter().map(|(x, _)| *x as f64).collect())
+        + axis_variance(positions.iter().map(|(_, y)| *y as f64).collect())
+}
+
+/// Counts the occupied tiles that have another occupied tile among their eight neighbours
+fn clustering(positions: &[(NumType, NumType)]) -> ResultType {
+    let occupied: HashSet<_> = positions.iter().copied().collect();
+    occupied
+        .iter()
+        .filter(|(x, y)| {
+            (-1..=1)
+                .flat_map(|dx| (-1..=1).map(move |dy| (dx, dy)))
+                .filter(|offset| *offset != (0, 0))
+                .any(|(dx, dy)| occupied.contains(&(x + dx, y + dy)))
+        })
+        .count()
+}
+
+/// The first second within one period with the best score of the robot positions
+fn find_tree(robots: &[Robot], area: &Area, score: Score) -> NumType {
+    (0..area.width * area.height)
+        .map(|seconds| {
+            let positions: Vec<_> = robots
+                .iter()
+                .map(|robot| robot.position_after(seconds, area))
+                .collect();
+            let value = score.evaluate(&positions, area).as_f64();
+            // Negated, so that the best score is always the lowest
+            let key = if score.lower_is_better() {
+                value
+            } else {
+                -value
+            };
+            (key, seconds)
+        })
+        .min_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map_or(0, |(_, seconds)| seconds)
+}
+
+/// Draws the area with `#` on every tile with at least one robot and `.` everywhere else
+fn render(positions: &[(NumType, NumType)], area: &Area) -> String {
+    let occupied: HashSet<_> = positions.iter().copied().collect();
+    let mut frame = String::new();
+    for y in 0..area.height {
+        for x in 0..area.width {
+            frame.push(if occupied.contains(&(x, y)) { '#' } else { '.' });
+        }
+        frame.push('\n');
+    }
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_safety_factor() {
+        let area = Area {
+            width: EXAMPLE_WIDTH,
+            height: EXAMPLE_HEIGHT,
+        };
+        let positions: Vec<_> = parse(EXAMPLE)
+            .unwrap()
+            .iter()
+            .map(|robot| robot.position_after(SECONDS, &area))
+            .collect();
+        assert_eq!(safety_factor(&positions, &area), 12);
+        assert_eq!(
+            Score::Quadrants.evaluate(&positions, &area),
+            Value::Count(12)
+        );
+        assert_eq!(
+            Score::Quadrants.evaluate(&positions, &area).to_string(),
+            "12"
+        );
+        assert_eq!(Value::Measure(2.5).to_string(), "2.5");
+    }
+
+    #[test]
+    fn even_area_has_no_middle() {
+        let area = Area {
+            width: 10,
+            height: 7,
+        };
+        // x = 5 is the first column of the right half, y = 3 is the middle row
+        let positions = [(4, 0), (5, 0), (0, 4), (9, 6), (5, 3)];
+        assert_eq!(safety_factor(&positions, &area), 1);
+        assert_eq!(safety_factor(&positions[1..], &area), 0);
+    }
+
+    #[test]
+    fn tree_frame() {
+        let area = Area {
+            width: 5,
+            height: 3,
+        };
+        // All three robots meet in the middle column after 2 seconds
+        let robots = parse("p=0,0 v=1,1\np=2,0 v=0,1\np=4,0 v=-1,1").unwrap();
+        assert_eq!(find_tree(&robots, &area, Score::Variance), 2);
+        // After 1 second they stand next to each other, which clustering prefers to a single tile
+        assert_eq!(find_tree(&robots, &area, Score::Clustering), 1);
+        let positions: Vec<_> = robots
+            .iter()
+            .map(|robot| robot.position_after(2, &area))
+            .collect();
+        assert_eq!(render(&positions, &area), ".....\n.....\n..#..\n");
+    }
+
+    #[test]
+    fn clustering_and_variance() {
+        let positions = [(0, 0), (1, 1), (5, 5), (0, 2)];
+        assert_eq!(clustering(&positions), 3);
+        assert_eq!(variance(&[(2, 3), (2, 3)]), 0.0);
+        assert_eq!(variance(&[(0, 0), (2, 4)]), 5.0);
+    }
+}
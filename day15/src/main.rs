@@ -0,0 +1,222 @@
+//! Day 15:
+//! A robot moves around a warehouse. The input is the map (`#` walls, `O` boxes, `@` the robot),
+//! followed by an empty line and the sequence of moves (`<`, `>`, `^`, `v`), which may span several lines.
+//! When the robot moves into a box, it pushes the box and every box directly behind it,
+//! unless the row of boxes would be pushed into a wall. Then nothing moves.
+//!
+//! The GPS coordinate of a box is 100 times its row plus its column.
+//! The answer is the sum of the GPS coordinates of all boxes after the robot has finished moving.
+//!
+//! `$ day15 --file-name <file>` to execute.
+//! `$ day15 --demo` runs on the example from the puzzle description instead.
+//!
+use aoc_common::grid::{Grid, Position};
+use aoc_common::{CommonArgs, Part};
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
+}
+
+type ResultType = usize;
+
+/// The example from the puzzle description, used by `--demo`
+const EXAMPLE: &str = "##########
+#..O..O.O#
+#......O.#
+#.OO..O.O#
+#..O@..O.#
+#O#..O...#
+#O..O..O.#
+#.OO.O.OO#
+#....O...#
+##########
+
+<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
+vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
+><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
+<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
+^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
+^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
+>^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
+<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
+^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
+v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let content = args.common.read_input(EXAMPLE)?;
+    let (mut warehouse, moves) = parse(&content)?;
+
+    args.common.solve(15, |part| match part {
+        Part::One => {
+            for direction in &moves {
+                warehouse.push(*direction);
+            }
+            Ok(warehouse.gps_sum())
+        }
+        Part::Two => Err(part.unsolved()),
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Tile {
+    Free,
+    Wall,
+    Box,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Moves one step from the position.
+    /// `parse` makes sure the warehouse is surrounded by walls, so this never leaves the map.
+    fn from(self, (row, col): Position) -> Position {
+        match self {
+            Direction::Up => (row - 1, col),
+            Direction::Right => (row, col + 1),
+            Direction::Down => (row + 1, col),
+            Direction::Left => (row, col - 1),
+        }
+    }
+}
+
+struct Warehouse {
+    tiles: Grid<Tile>,
+    robot: Position,
+}
+
+impl Warehouse {
+    /// Moves the robot one step, pushing the boxes in front of it if there is room behind them
+    fn push(&mut self, direction: Direction) {
+        let next = direction.from(self.robot);
+        let mut end = next;
+        while self.tiles[end] == Tile::Box {
+            end = direction.from(end);
+        }
+        if self.tiles[end] == Tile::Wall {
+            return;
+        }
+        // Moving a row of boxes by one is the same as moving the first box to the end.
+        if end != next {
+            self.tiles[end] = Tile::Box;
+            self.tiles[next] = Tile::Free;
+        }
+        self.robot = next;
+    }
+
+    fn gps_sum(&self) -> ResultType {
+        self.tiles
+            .positions()
+            .filter(|position| self.tiles[*position] == Tile::Box)
+            .map(|(row, col)| 100 * row as usize + col as usize)
+            .sum()
+    }
+}
+
+/// Reads the warehouse and the moves of the robot
+fn parse(content: &str) -> Result<(Warehouse, Vec<Direction>), Box<dyn std::error::Error>> {
+    let content = content.replace("\r\n", "\n");
+    let (map, moves) = content
+        .trim()
+        .split_once("\n\n")
+        .ok_or("Expected the map and the moves separated by an empty line")?;
+
+    let mut robot = None;
+    let tiles = Grid::parse(map, |position, c| match c {
+        '.' => Some(Tile::Free),
+        '#' => Some(Tile::Wall),
+        'O' => Some(Tile::Box),
+        '@' => {
+            robot = Some(position);
+            Some(Tile::Free)
+        }
+        _ => None,
+    })?;
+
+    if !enclosed(&tiles) {
+        return Err("The map must be surrounded by walls".into());
+    }
+
+    let moves = moves
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c {
+            '^' => Ok(Direction::Up),
+            '>' => Ok(Direction::Right),
+            'v' => Ok(Direction::Down),
+            '<' => Ok(Direction::Left),
+            _ => Err(format!("Unexpected move {:?}", c)),
+        })
+        .collect::<Result<_, _>>()?;
+
+    let warehouse = Warehouse {
+        tiles,
+        robot: robot.ok_or("No robot in the warehouse")?,
+    };
+    Ok((warehouse, moves))
+}
+
+/// Whether the map is not empty and the outermost tiles are all walls,
+/// which [`Direction::from`] relies on to never leave the map
+fn enclosed(tiles: &Grid<Tile>) -> bool {
+    let (width, height) = (tiles.width() as isize, tiles.height() as isize);
+    width > 0
+        && tiles.positions().all(|(row, col)| {
+            let inside = (1..height - 1).contains(&row) && (1..width - 1).contains(&col);
+            inside || tiles[(row, col)] == Tile::Wall
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMALL_EXAMPLE: &str = "########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv<v>>v<<
+";
+
+    fn simulate(content: &str) -> ResultType {
+        let (mut warehouse, moves) = parse(content).unwrap();
+        for direction in moves {
+            warehouse.push(direction);
+        }
+        warehouse.gps_sum()
+    }
+
+    #[test]
+    fn small_example_gps_sum() {
+        assert_eq!(simulate(SMALL_EXAMPLE), 2028);
+    }
+
+    #[test]
+    fn example_gps_sum() {
+        assert_eq!(simulate(EXAMPLE), 10092);
+    }
+
+    #[test]
+    fn unenclosed_map() {
+        assert!(parse("@O.\n\n<<").is_err());
+        assert!(parse("####\n#@.#\n#.#\n####\n\nv").is_err());
+        assert!(parse("####\n#@.#\n####\n\n<").is_ok());
+    }
+}
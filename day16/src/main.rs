@@ -0,0 +1,169 @@
+//! Day 16:
+//! Reindeer race through a maze from the start tile `S` to the end tile `E`, starting out facing east.
+//! Walls are marked with `#`. Moving one tile forward costs 1 point,
+//! rotating clockwise or counterclockwise by 90 degrees costs 1000 points.
+//! The answer is the lowest score a reindeer could possibly get.
+//!
+//! `$ day16 --file-name <file>` to execute.
+//! `$ day16 --demo` runs on the example from the puzzle description instead.
+//!
+use aoc_common::grid::{Grid, Position};
+use aoc_common::{CommonArgs, Part};
+use clap::Parser;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
+}
+
+type ResultType = u64;
+
+const MOVE_COST: ResultType = 1;
+const TURN_COST: ResultType = 1000;
+
+/// The example from the puzzle description, used by `--demo`
+const EXAMPLE: &str = "###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let content = args.common.read_input(EXAMPLE)?;
+    let maze = parse(&content)?;
+
+    args.common.solve(16, |part| match part {
+        Part::One => lowest_score(&maze).ok_or_else(|| "The end cannot be reached.".into()),
+        Part::Two => Err(part.unsolved()),
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    fn turn_right(self) -> Self {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    fn turn_left(self) -> Self {
+        self.turn_right().turn_right().turn_right()
+    }
+
+    /// The neighbouring position in the direction, which may be off the map
+    fn from(self, (row, col): Position) -> Position {
+        match self {
+            Direction::North => (row - 1, col),
+            Direction::East => (row, col + 1),
+            Direction::South => (row + 1, col),
+            Direction::West => (row, col - 1),
+        }
+    }
+}
+
+struct Maze {
+    walls: Grid<bool>,
+    start: Position,
+    end: Position,
+}
+
+impl Maze {
+    fn is_open(&self, position: Position) -> bool {
+        self.walls.get(position) == Some(&false)
+    }
+}
+
+fn parse(content: &str) -> Result<Maze, Box<dyn std::error::Error>> {
+    let mut start = None;
+    let mut end = None;
+    let walls = Grid::parse(content, |position, c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        'S' => {
+            start = Some(position);
+            Some(false)
+        }
+        'E' => {
+            end = Some(position);
+            Some(false)
+        }
+        _ => None,
+    })?;
+
+    Ok(Maze {
+        walls,
+        start: start.ok_or("No start tile in the maze")?,
+        end: end.ok_or("No end tile in the maze")?,
+    })
+}
+
+/// Dijkstra over the states `(position, facing)`, starting east on the start tile.
+/// Returns `None` if the end tile cannot be reached.
+fn lowest_score(maze: &Maze) -> Option<ResultType> {
+    let start = (maze.start, Direction::East);
+    let mut best = HashMap::from([(start, 0)]);
+    let mut queue = BinaryHeap::from([Reverse((0, start))]);
+
+    while let Some(Reverse((score, (position, facing)))) = queue.pop() {
+        if position == maze.end {
+            return Some(score);
+        }
+        if best.get(&(position, facing)).is_some_and(|b| *b < score) {
+            continue;
+        }
+
+        let next = facing.from(position);
+        let forward = maze
+            .is_open(next)
+            .then_some((score + MOVE_COST, (next, facing)));
+        let turns = [facing.turn_left(), facing.turn_right()]
+            .map(|turned| (score + TURN_COST, (position, turned)));
+
+        for (next_score, state) in forward.into_iter().chain(turns) {
+            if best.get(&state).is_none_or(|b| next_score < *b) {
+                best.insert(state, next_score);
+                queue.push(Reverse((next_score, state)));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_lowest_score() {
+        let maze = parse(EXAMPLE).unwrap();
+        assert_eq!(lowest_score(&maze), Some(7036));
+    }
+}
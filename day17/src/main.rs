@@ -0,0 +1,82 @@
+//! Day 17:
+//! A 3-bit computer has the three registers A, B and C and a program of 3-bit numbers,
+//! read as pairs of an opcode and its operand. The eight instructions divide, xor,
+//! store, jump and output values; see [`vm::Computer::step`].
+//! The computer halts when it tries to read an opcode past the end of the program.
+//!
+//! The answer is everything the program outputs, joined with commas.
+//!
+//! `$ day17 --file-name <file> [--max-steps <n>]` to execute.
+//! `$ day17 --demo` runs on the example from the puzzle description instead.
+//! The computer gives up after `--max-steps` instructions, as a program may loop forever.
+//!
+mod vm;
+
+use aoc_common::{CommonArgs, Part};
+use clap::Parser;
+use vm::Computer;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// Give up if the program has not halted after this many instructions
+    #[clap(long, default_value_t = MAX_STEPS)]
+    max_steps: usize,
+}
+
+const MAX_STEPS: usize = 10_000_000;
+
+/// The example from the puzzle description, used by `--demo`
+const EXAMPLE: &str = "Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let content = args.common.read_input(EXAMPLE)?;
+    let mut computer = Computer::parse(&content)?;
+
+    args.common.solve(17, |part| match part {
+        Part::One => {
+            computer.run(args.max_steps)?;
+            Ok(computer.output_string())
+        }
+        Part::Two => Err(part.unsolved()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_output() {
+        let mut computer = Computer::parse(EXAMPLE).unwrap();
+        computer.run(MAX_STEPS).unwrap();
+        assert_eq!(computer.output_string(), "4,6,3,5,6,3,5,2,1,0");
+    }
+
+    #[test]
+    fn single_steps() {
+        let mut computer =
+            Computer::parse("Register A: 0\nRegister B: 0\nRegister C: 9\n\nProgram: 2,6").unwrap();
+        assert!(computer.step().unwrap());
+        assert_eq!(computer.b, 1);
+        assert!(!computer.step().unwrap());
+    }
+
+    #[test]
+    fn endless_program() {
+        let mut computer =
+            Computer::parse("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 3,0").unwrap();
+        assert!(computer.run(1000).is_err());
+        assert_eq!(computer.ip, 0);
+    }
+}
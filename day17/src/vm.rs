@@ -0,0 +1,137 @@
+//! The 3-bit computer, kept separate from the puzzle so it can be stepped through one instruction at a time.
+
+/// The registers hold integers of any size, the program only 3-bit numbers
+pub type Word = u64;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Computer {
+    pub a: Word,
+    pub b: Word,
+    pub c: Word,
+    pub program: Vec<u8>,
+    /// Index of the next opcode in the program
+    pub ip: usize,
+    pub output: Vec<Word>,
+}
+
+impl Computer {
+    /// Reads the three registers, then the program:
+    ///
+    /// ```text
+    /// Register A: 729
+    /// Register B: 0
+    /// Register C: 0
+    ///
+    /// Program: 0,1,5,4,3,0
+    /// ```
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut lines = content.lines().map(str::trim).filter(|l| !l.is_empty());
+        let mut register = |name: &str| -> Result<Word, String> {
+            let line = lines.next().ok_or(format!("Missing register {}", name))?;
+            line.strip_prefix(&format!("Register {}:", name))
+                .ok_or(format!("Expected register {}, got {}", name, line))?
+                .trim()
+                .parse()
+                .map_err(|e| format!("Invalid register {}: {}", name, e))
+        };
+        let (a, b, c) = (register("A")?, register("B")?, register("C")?);
+
+        let line = lines.next().ok_or("Missing program")?;
+        let program = line
+            .strip_prefix("Program:")
+            .ok_or(format!("Expected the program, got {}", line))?
+            .split(',')
+            .map(|n| match n.trim().parse() {
+                Ok(n) if n < 8 => Ok(n),
+                _ => Err(format!("Invalid 3-bit number {:?}", n)),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Computer {
+            a,
+            b,
+            c,
+            program,
+            ip: 0,
+            output: Vec::new(),
+        })
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.ip + 1 >= self.program.len()
+    }
+
+    /// The value of a combo operand: 0 to 3 are literal, 4 to 6 are the registers A to C
+    fn combo(&self, operand: u8) -> Result<Word, String> {
+        match operand {
+            0..=3 => Ok(operand as Word),
+            4 => Ok(self.a),
+            5 => Ok(self.b),
+            6 => Ok(self.c),
+            _ => Err(format!("Invalid combo operand {}", operand)),
+        }
+    }
+
+    /// Division by a power of two, which is just a shift. Shifting out every bit gives 0.
+    fn dv(&self, operand: u8) -> Result<Word, String> {
+        let shift = self.combo(operand)?;
+        Ok(u32::try_from(shift)
+            .ok()
+            .and_then(|shift| self.a.checked_shr(shift))
+            .unwrap_or(0))
+    }
+
+    /// Executes a single instruction.
+    /// Returns `false` without doing anything if the computer has already halted.
+    pub fn step(&mut self) -> Result<bool, String> {
+        if self.is_halted() {
+            return Ok(false);
+        }
+        let (opcode, operand) = (self.program[self.ip], self.program[self.ip + 1]);
+        let mut next = self.ip + 2;
+        match opcode {
+            0 => self.a = self.dv(operand)?,
+            1 => self.b ^= operand as Word,
+            2 => self.b = self.combo(operand)? % 8,
+            3 => {
+                if self.a != 0 {
+                    next = operand as usize;
+                }
+            }
+            4 => self.b ^= self.c,
+            5 => self.output.push(self.combo(operand)? % 8),
+            6 => self.b = self.dv(operand)?,
+            7 => self.c = self.dv(operand)?,
+            _ => unreachable!("the program only contains 3-bit numbers"),
+        }
+        self.ip = next;
+        Ok(true)
+    }
+
+    /// Steps until the computer halts.
+    /// Fails after `max_steps` instructions, as the program may jump back forever.
+    pub fn run(&mut self, max_steps: usize) -> Result<(), String> {
+        for _ in 0..max_steps {
+            if !self.step()? {
+                return Ok(());
+            }
+        }
+        if self.is_halted() {
+            Ok(())
+        } else {
+            Err(format!(
+                "The program did not halt within {} steps",
+                max_steps
+            ))
+        }
+    }
+
+    /// The output joined with commas
+    pub fn output_string(&self) -> String {
+        self.output
+            .iter()
+            .map(Word::to_string)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
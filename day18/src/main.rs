@@ -0,0 +1,158 @@
+//! Day 18:
+//! Bytes fall into a square memory grid, one coordinate `x,y` per line, and corrupt the tile they land on.
+//! After the first bytes have fallen, the answer is the minimum number of steps
+//! from the top left corner to the bottom right corner, moving horizontally or vertically
+//! and never onto a corrupted tile.
+//!
+//! `$ day18 --file-name <file> [--size <n>] [--bytes <n>]` to execute.
+//! The grid is 71 tiles wide and 1024 bytes fall by default.
+//! `$ day18 --demo` runs on the example from the puzzle description instead,
+//! which uses a grid of 7 tiles and 12 bytes unless they are given.
+//!
+use aoc_common::grid::Grid;
+use aoc_common::{CommonArgs, Part};
+use clap::Parser;
+use std::collections::VecDeque;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// Width and height of the grid [default: 71, or 7 with --demo]
+    #[clap(long)]
+    size: Option<usize>,
+
+    /// How many bytes fall before searching the path [default: 1024, or 12 with --demo]
+    #[clap(long)]
+    bytes: Option<usize>,
+}
+
+type ResultType = usize;
+
+/// A position as `(x, y)`
+type Position = (usize, usize);
+
+const SIZE: usize = 71;
+const BYTES: usize = 1024;
+
+const EXAMPLE_SIZE: usize = 7;
+const EXAMPLE_BYTES: usize = 12;
+
+/// The example from the puzzle description, used by `--demo`
+const EXAMPLE: &str = "5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+1,2
+5,5
+2,5
+6,5
+1,4
+0,4
+6,4
+1,1
+6,1
+1,0
+0,5
+1,6
+2,0
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let content = args.common.read_input(EXAMPLE)?;
+    let (size, bytes) = if args.common.demo {
+        (EXAMPLE_SIZE, EXAMPLE_BYTES)
+    } else {
+        (SIZE, BYTES)
+    };
+    let size = args.size.unwrap_or(size);
+    let bytes = args.bytes.unwrap_or(bytes);
+    if size == 0 {
+        return Err("The grid needs a positive size".into());
+    }
+
+    let falling = parse(&content)?;
+    if let Some((x, y)) = falling.iter().find(|(x, y)| *x >= size || *y >= size) {
+        return Err(format!("Byte {},{} falls outside of the grid", x, y).into());
+    }
+
+    args.common.solve(18, |part| match part {
+        Part::One => shortest_path(size, &falling[..bytes.min(falling.len())])
+            .ok_or_else(|| "The exit cannot be reached.".into()),
+        Part::Two => Err(part.unsolved()),
+    })
+}
+
+fn parse(content: &str) -> Result<Vec<Position>, Box<dyn std::error::Error>> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let (x, y) = line
+                .split_once(',')
+                .ok_or_else(|| format!("Expected x,y, got {}", line))?;
+            Ok((x.parse()?, y.parse()?))
+        })
+        .collect()
+}
+
+/// Breadth first search from the top left to the bottom right corner of the grid.
+/// Returns `None` if the corrupted tiles block every path.
+fn shortest_path(size: usize, corrupted: &[Position]) -> Option<ResultType> {
+    // The grid is indexed by `(row, column)`, which is `(y, x)`
+    let mut blocked = Grid::new(size, size, false);
+    for (x, y) in corrupted {
+        blocked[(*y as isize, *x as isize)] = true;
+    }
+
+    let start = (0, 0);
+    let end = (size as isize - 1, size as isize - 1);
+    if blocked[start] {
+        return None;
+    }
+
+    let mut steps = Grid::new(size, size, None);
+    steps[start] = Some(0);
+    let mut queue = VecDeque::from([start]);
+    while let Some(position) = queue.pop_front() {
+        let current = steps[position]?;
+        if position == end {
+            return Some(current);
+        }
+        for next in blocked.neighbours(position) {
+            if !blocked[next] && steps[next].is_none() {
+                steps[next] = Some(current + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_shortest_path() {
+        let falling = parse(EXAMPLE).unwrap();
+        assert_eq!(
+            shortest_path(EXAMPLE_SIZE, &falling[..EXAMPLE_BYTES]),
+            Some(22)
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! Day 19:
+//! The first line lists the available towel patterns, separated by commas, e.g. `r, wr, b, g`.
+//! After an empty line, every line is a design of stripe colours that should be arranged from towels.
+//! Each pattern is available as often as needed.
+//! The answer is the number of designs that can be formed from the patterns.
+//!
+//! Part 2: The answer is the sum of the number of different arrangements of every design.
+//!
+//! `$ day19 --file-name <file> [--part 2]` to execute.
+//! `$ day19 --demo` runs on the example from the puzzle description instead.
+//!
+use aoc_common::{CommonArgs, Part};
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
+}
+
+type ResultType = u128;
+
+/// The example from the puzzle description, used by `--demo`
+const EXAMPLE: &str = "r, wr, b, g, bwu, rb, gb, br
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrgwb
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let content = args.common.read_input(EXAMPLE)?;
+    let (patterns, designs) = parse(&content)?;
+
+    args.common.solve(19, |part| match part {
+        Part::One => Ok(count_possible(&patterns, &designs)),
+        Part::Two => total_arrangements(&patterns, &designs),
+    })
+}
+
+/// Reads the patterns and the designs
+fn parse(content: &str) -> Result<(Vec<&str>, Vec<&str>), String> {
+    let mut lines = content.lines().map(str::trim);
+    let patterns = lines
+        .next()
+        .ok_or("Missing the towel patterns")?
+        .split(',')
+        .map(str::trim)
+        .filter(|pattern| !pattern.is_empty())
+        .collect();
+    let designs = lines.filter(|line| !line.is_empty()).collect();
+    Ok((patterns, designs))
+}
+
+/// Whether the design can be composed from the patterns.
+/// `possible[i]` tells if the first `i` stripes can be composed,
+/// which is the case if a pattern extends a possible shorter prefix to length `i`.
+fn is_possible(design: &str, patterns: &[&str]) -> bool {
+    let mut possible = vec![false; design.len() + 1];
+    possible[0] = true;
+    for start in 0..design.len() {
+        if !possible[start] {
+            continue;
+        }
+        for pattern in patterns {
+            if design[start..].starts_with(pattern) {
+                possible[start + pattern.len()] = true;
+            }
+        }
+    }
+    possible[design.len()]
+}
+
+/// Counts the ways the design can be composed from the patterns, like [`is_possible`]
+/// but adding up the compositions of every shorter prefix.
+/// The counts grow exponentially with the length of the design, so overflowing is an error.
+fn arrangements(design: &str, patterns: &[&str]) -> Result<ResultType, String> {
+    let mut ways: Vec<ResultType> = vec![0; design.len() + 1];
+    ways[0] = 1;
+    for start in 0..design.len() {
+        if ways[start] == 0 {
+            continue;
+        }
+        for pattern in patterns {
+            if design[start..].starts_with(pattern) {
+                let end = start + pattern.len();
+                ways[end] = ways[end]
+                    .checked_add(ways[start])
+                    .ok_or_else(|| format!("The arrangements of {} exceed u128", design))?;
+            }
+        }
+    }
+    Ok(ways[design.len()])
+}
+
+fn count_possible(patterns: &[&str], designs: &[&str]) -> ResultType {
+    designs
+        .iter()
+        .filter(|design| is_possible(design, patterns))
+        .count() as ResultType
+}
+
+fn total_arrangements(
+    patterns: &[&str],
+    designs: &[&str],
+) -> Result<ResultType, Box<dyn std::error::Error>> {
+    designs.iter().try_fold(0, |total: ResultType, design| {
+        total
+            .checked_add(arrangements(design, patterns)?)
+            .ok_or_else(|| "The total arrangements exceed u128".into())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_possible_designs() {
+        let (patterns, designs) = parse(EXAMPLE).unwrap();
+        assert_eq!(count_possible(&patterns, &designs), 6);
+        assert_eq!(arrangements("gbbr", &patterns).unwrap(), 4);
+        assert_eq!(total_arrangements(&patterns, &designs).unwrap(), 16);
+    }
+
+    #[test]
+    fn arrangements_overflow() {
+        // The arrangements of `a` repeated n times from `a` and `aa` are the Fibonacci number F(n + 1)
+        let patterns = ["a", "aa"];
+        assert_eq!(arrangements(&"a".repeat(10), &patterns).unwrap(), 89);
+        assert!(arrangements(&"a".repeat(100), &patterns).unwrap() > u64::MAX as ResultType);
+        assert!(arrangements(&"a".repeat(200), &patterns).is_err());
+        assert!(is_possible(&"a".repeat(200), &patterns));
+    }
+}
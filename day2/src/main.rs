@@ -16,53 +16,181 @@
 //!
 //! (Task 2)
 //! The dampener parameter says how many violations are okay for something to be considered safe
+//! Build with `--features parallel` to try the removals on multiple threads.
+//!
+//! With `--demo` the example above is used instead of an input file.
+//! With `-d|--dampen` or `--part 2` the dampener is on.
+//! With `--explain` the verdict of every line is printed with its reason (meant for small inputs).
+//! With `--write-safe <file>` all safe lines are written to that file. With dampening,
+//! lines that are only safe after removing a level are written without that level.
+//! `--help` lists the flags shared with the other days, like `--quiet` and `--output kv`.
 //!
 
+use aoc_common::search::first_successful_modification;
+use aoc_common::{CommonArgs, Part};
 use clap::Parser;
+use iterator_utils::IterUtils;
 use std::path::PathBuf;
 
 type NumType = i32;
 type ResultType = usize;
 
+/// The example from the puzzle description, used by `--demo`
+const EXAMPLE: &str = "7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9
+";
+
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Args {
-    #[arg(short, long)]
-    file_name: PathBuf,
+    #[command(flatten)]
+    common: CommonArgs,
 
-    #[clap(short, long, default_value = "false")]
+    /// Allow removing a single level, the same as `--part 2`
+    #[clap(short, long, default_value = "false", conflicts_with_all = ["part", "both"])]
     dampen: bool,
+
+    /// Print the verdict of every line with its reason
+    #[clap(long, default_value = "false", conflicts_with_all = ["quiet", "output"])]
+    explain: bool,
+
+    /// Write all safe lines to this file, repaired if part 2 is solved
+    #[clap(long)]
+    write_safe: Option<PathBuf>,
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args = Args::parse();
+    let mut args = Args::parse();
+    if args.dampen {
+        args.common.part = Part::Two;
+    }
 
-    println!("Reading file {}.", args.file_name.display());
-    println!("Status dampening is {}.", if args.dampen { "on" } else { "off" });
+    let content = args.common.read_input(EXAMPLE)?;
+    let lines = read_lists(content)?;
 
-    let content = std::fs::read_to_string(&args.file_name)?;
-    let lines = read_lists(content);
+    args.common.solve(2, |part| {
+        let dampen = part == Part::Two;
+        if args.explain {
+            for line in explain_lists(&lines, dampen) {
+                println!("{}", line);
+            }
+        }
 
+        Ok(if dampen {
+            check_lists_dampended(&lines)
+        } else {
+            check_lists(&lines)
+        })
+    })?;
 
-    let answer = if args.dampen {
-        check_lists_dampended(&lines)
-    } else {
-        check_lists(&lines)
-    };
-    println!("{}", answer);
+    if let Some(path) = &args.write_safe {
+        let dampen = args.common.parts().contains(&Part::Two);
+        let content: String = safe_lists(&lines, dampen)
+            .iter()
+            .map(|list| format_list(list) + "\n")
+            .collect();
+        std::fs::write(path, content)?;
+    }
 
     Ok(())
 }
 
-fn read_lists(content: String) -> Vec<Vec<NumType>> {
+/// Reads one list of numbers per line, skipping empty lines.
+/// Fails with the line number on anything that is not a number.
+fn read_lists(content: String) -> Result<Vec<Vec<NumType>>, String> {
     content
         .lines()
-        .map(|line| {
-            line.split(' ')
-                .filter_map(|s| s.parse::<NumType>().ok())
-                .collect::<Vec<_>>()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            line.split_whitespace()
+                .map(|s| {
+                    s.parse::<NumType>()
+                        .map_err(|e| format!("Line {}: invalid number {:?}: {}", i + 1, s, e))
+                })
+                .collect::<Result<Vec<_>, _>>()
         })
+        .collect()
+}
+
+/// Formats the list the same way as the input, separated by single spaces
+fn format_list(list: &[NumType]) -> String {
+    list.iter()
+        .map(|n| n.to_string())
         .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// All safe lists. With dampening, unsafe lists that can be fixed by removing
+/// a single level are included with that level removed.
+fn safe_lists(lists: &[Vec<NumType>], dampen: bool) -> Vec<Vec<NumType>> {
+    lists
+        .iter()
+        .filter_map(|list| {
+            if check_list(list.iter().copied()) {
+                Some(list.clone())
+            } else if dampen {
+                first_successful_modification(list, without, check_list)
+                    .map(|i| without(list, i).collect())
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Describes the verdict of every list and the reason for it, one line per list
+fn explain_lists(lists: &[Vec<NumType>], dampen: bool) -> Vec<String> {
+    let mut lines = Vec::new();
+    for list in lists {
+        let text = format_list(list);
+        match find_violation(list) {
+            None => lines.push(format!("{}: safe", text)),
+            Some(reason) if dampen => {
+                let fix = first_successful_modification(list, without, check_list);
+                lines.push(match fix {
+                    Some(i) => format!("{}: safe after removing {} ({})", text, list[i], reason),
+                    None => format!("{}: unsafe, {} and no single removal helps", text, reason),
+                });
+            }
+            Some(reason) => lines.push(format!("{}: unsafe, {}", text, reason)),
+        }
+    }
+    lines
+}
+
+/// Describes the first violation of the rules in the list, or `None` if it is safe
+fn find_violation(list: &[NumType]) -> Option<String> {
+    let mut direction = 0;
+    for (last, num) in list.iter().pairwise() {
+        let diff = num - last;
+        if diff == 0 {
+            return Some(format!(
+                "{} -> {} neither increases nor decreases",
+                last, num
+            ));
+        }
+        if diff.abs() > 3 {
+            return Some(format!("{} -> {} changes by {:+}", last, num, diff));
+        }
+        if direction != 0 && diff.signum() != direction {
+            return Some(format!("{} -> {} changes direction", last, num));
+        }
+        direction = diff.signum();
+    }
+    None
+}
+
+/// The list without the element at `skip`
+fn without(list: &[NumType], skip: usize) -> impl Iterator<Item = NumType> + '_ {
+    list.iter()
+        .enumerate()
+        .filter(move |(index, _)| *index != skip)
+        .map(|(_, value)| *value)
 }
 
 fn check_lists_dampended(lists: &[Vec<NumType>]) -> ResultType {
@@ -70,15 +198,7 @@ fn check_lists_dampended(lists: &[Vec<NumType>]) -> ResultType {
         .iter()
         .filter(|list| {
             if !check_list(list.iter().copied()) {
-                (0..list.len()).into_iter().filter(|i| {
-                    check_list(list.into_iter().enumerate().filter_map(|(index, value)| {
-                        if index == *i {
-                            None
-                        } else {
-                            Some(*value)
-                        }
-                    }))
-                }).next().is_some()
+                first_successful_modification(list, without, check_list).is_some()
             } else {
                 true
             }
@@ -90,30 +210,80 @@ fn check_lists(lists: &[Vec<NumType>]) -> ResultType {
 }
 
 fn check_list<I: IntoIterator<Item = NumType>>(list: I) -> bool {
-    let mut iter = list.into_iter();
+    let mut ascending: bool = true;
+    let mut descending: bool = true;
 
-    if let Some(mut last) = iter.next() {
-        let mut ascending: bool = true;
-        let mut descending: bool = true;
-
-        for num in iter {
-            let diff = last - num;
-            if 0 == diff || diff.abs() > 3 {
-                return false;
-            }
-            if diff < 0 {
-                descending = false;
-            }
-            if diff > 0 {
-                ascending = false;
-            }
-            if !ascending && !descending {
-                return false;
-            }
-            last = num;
+    for (last, num) in list.into_iter().pairwise() {
+        let diff = last - num;
+        if 0 == diff || diff.abs() > 3 {
+            return false;
+        }
+        if diff < 0 {
+            descending = false;
         }
-        true
-    } else {
-        true
+        if diff > 0 {
+            ascending = false;
+        }
+        if !ascending && !descending {
+            return false;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_safe_lists() {
+        assert_eq!(check_lists(&read_lists(EXAMPLE.to_string()).unwrap()), 2);
+    }
+
+    #[test]
+    fn example_safe_lists_dampened() {
+        assert_eq!(
+            check_lists_dampended(&read_lists(EXAMPLE.to_string()).unwrap()),
+            4
+        );
+    }
+
+    #[test]
+    fn malformed_lists() {
+        assert!(read_lists("7 6 4 2 1\n1 2 x 8 9\n".to_string())
+            .unwrap_err()
+            .starts_with("Line 2: invalid number \"x\""));
+        assert_eq!(
+            read_lists("1 2\n\n3\n".to_string()).unwrap(),
+            [vec![1, 2], vec![3]]
+        );
+    }
+
+    #[test]
+    fn example_safe_lists_repaired() {
+        let lines = read_lists(EXAMPLE.to_string()).unwrap();
+        assert_eq!(
+            safe_lists(&lines, true),
+            vec![
+                vec![7, 6, 4, 2, 1],
+                vec![1, 2, 4, 5],
+                vec![8, 6, 4, 1],
+                vec![1, 3, 6, 7, 9],
+            ]
+        );
+    }
+
+    #[test]
+    fn example_explain() {
+        insta::assert_snapshot!(
+            explain_lists(&read_lists(EXAMPLE.to_string()).unwrap(), false).join("\n")
+        );
+    }
+
+    #[test]
+    fn example_explain_dampened() {
+        insta::assert_snapshot!(
+            explain_lists(&read_lists(EXAMPLE.to_string()).unwrap(), true).join("\n")
+        );
     }
 }
@@ -0,0 +1,70 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+fn day2() -> Command {
+    Command::cargo_bin("day2").unwrap()
+}
+
+#[test]
+fn quiet_safe_lists() {
+    day2()
+        .args(["--file-name", "tests/example.txt", "--quiet"])
+        .assert()
+        .success()
+        .stdout("2\n");
+}
+
+#[test]
+fn quiet_safe_lists_dampened() {
+    day2()
+        .args(["--file-name", "tests/example.txt", "--dampen", "--quiet"])
+        .assert()
+        .success()
+        .stdout("4\n");
+}
+
+#[test]
+fn kv_output() {
+    day2()
+        .args(["--file-name", "tests/example.txt", "--output", "kv"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("day=2 part=1 answer=2 parse_us="));
+}
+
+#[test]
+fn both_parts() {
+    day2()
+        .args(["--file-name", "tests/example.txt", "--both", "--color", "never"])
+        .assert()
+        .success()
+        .stdout("Reading file tests/example.txt.\nPart 1: 2\nPart 2: 4\n");
+}
+
+#[test]
+fn demo_without_file() {
+    day2()
+        .args(["--demo", "--dampen", "--quiet"])
+        .assert()
+        .success()
+        .stdout("4\n");
+}
+
+#[test]
+fn missing_file() {
+    day2()
+        .args(["--file-name", "tests/does_not_exist.txt"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::starts_with("Error:"));
+}
+
+#[test]
+fn malformed_input() {
+    day2()
+        .args(["--file-name", "tests/malformed.txt", "--quiet"])
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(predicate::str::contains("Line 2: invalid number"));
+}
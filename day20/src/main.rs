@@ -0,0 +1,167 @@
+//! Day 20:
+//! A racetrack leads from `S` to `E` through walls `#`, along a single path without branches.
+//! Once during a race, a program may cheat by passing through walls for up to 2 steps,
+//! ending back on the track. The cheat saves the difference between the distances
+//! along the track, minus the steps taken while cheating.
+//! The answer is the number of cheats that save at least 100 picoseconds.
+//!
+//! `$ day20 --file-name <file> [--min-saving <n>]` to execute.
+//! `$ day20 --demo` runs on the example from the puzzle description instead.
+//! No cheat in the example saves 100 picoseconds, so it counts the cheats saving at least 20 unless given.
+//!
+use aoc_common::grid::{Grid, Position};
+use aoc_common::{CommonArgs, Part};
+use clap::Parser;
+use std::collections::HashMap;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// Only count cheats saving at least this many picoseconds [default: 100, or 20 with --demo]
+    #[clap(long)]
+    min_saving: Option<usize>,
+}
+
+type ResultType = usize;
+
+const MIN_SAVING: usize = 100;
+const EXAMPLE_MIN_SAVING: usize = 20;
+
+/// How many steps a cheat may pass through walls
+const CHEAT_LENGTH: isize = 2;
+
+/// The example from the puzzle description, used by `--demo`
+const EXAMPLE: &str = "###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let content = args.common.read_input(EXAMPLE)?;
+    let min_saving = args.min_saving.unwrap_or(if args.common.demo {
+        EXAMPLE_MIN_SAVING
+    } else {
+        MIN_SAVING
+    });
+
+    let track = parse(&content)?;
+
+    args.common.solve(20, |part| match part {
+        Part::One => Ok(count_cheats(&track, CHEAT_LENGTH, min_saving)),
+        Part::Two => Err(part.unsolved()),
+    })
+}
+
+/// Follows the track from the start to the end and returns its tiles in order,
+/// so that the index of a tile is its distance from the start
+fn parse(content: &str) -> Result<Vec<Position>, Box<dyn std::error::Error>> {
+    let mut start = None;
+    let mut end = None;
+    let open = Grid::parse(content, |position, c| match c {
+        '#' => Some(false),
+        '.' => Some(true),
+        'S' => {
+            start = Some(position);
+            Some(true)
+        }
+        'E' => {
+            end = Some(position);
+            Some(true)
+        }
+        _ => None,
+    })?;
+    let start = start.ok_or("No start on the track")?;
+    let end = end.ok_or("No end on the track")?;
+
+    let mut track = vec![start];
+    let mut visited = Grid::new(open.width(), open.height(), false);
+    visited[start] = true;
+    let mut previous = None;
+    let mut current = start;
+    while current != end {
+        let next: Vec<Position> = open
+            .neighbours(current)
+            .filter(|next| Some(*next) != previous && open[*next])
+            .collect();
+        if next.iter().any(|next| visited[*next]) {
+            return Err(format!("The track runs in a loop at {:?}", current).into());
+        }
+        previous = Some(current);
+        current = match next[..] {
+            [next] => next,
+            [] => return Err("The track has a dead end".into()),
+            _ => return Err(format!("The track branches at {:?}", previous.unwrap()).into()),
+        };
+        visited[current] = true;
+        track.push(current);
+    }
+    Ok(track)
+}
+
+/// Counts the pairs of track tiles at most `cheat_length` steps apart,
+/// where jumping from the earlier to the later one saves at least `min_saving` steps
+fn count_cheats(track: &[Position], cheat_length: isize, min_saving: usize) -> ResultType {
+    let distance: HashMap<Position, usize> = track
+        .iter()
+        .enumerate()
+        .map(|(distance, position)| (*position, distance))
+        .collect();
+
+    let mut count = 0;
+    for (from, (row, col)) in track.iter().enumerate() {
+        for d_row in -cheat_length..=cheat_length {
+            let remaining = cheat_length - d_row.abs();
+            for d_col in -remaining..=remaining {
+                let steps = (d_row.abs() + d_col.abs()) as usize;
+                if let Some(to) = distance.get(&(row + d_row, col + d_col)) {
+                    if *to >= from + steps + min_saving {
+                        count += 1;
+                    }
+                }
+            }
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_cheats() {
+        let track = parse(EXAMPLE).unwrap();
+        assert_eq!(track.len(), 85);
+        assert_eq!(count_cheats(&track, CHEAT_LENGTH, 64), 1);
+        assert_eq!(count_cheats(&track, CHEAT_LENGTH, EXAMPLE_MIN_SAVING), 5);
+        assert_eq!(count_cheats(&track, CHEAT_LENGTH, 2), 44);
+    }
+
+    #[test]
+    fn malformed_tracks() {
+        // The track forks right after the start
+        assert!(parse("#####\n#S..#\n#.#E#\n#####").is_err());
+        // A loop that never reaches the end, entered where the track forks
+        assert!(parse("######\n#S...#\n##.#.#\n##...#\n####E#\n######").is_err());
+        // A dead end before the end
+        assert!(parse("#####\n#S.##\n###E#\n#####").is_err());
+        assert_eq!(parse("#####\n#S.E#\n#####").unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,233 @@
+//! Day 21:
+//! Codes like `029A` have to be typed on a numeric keypad by a robot arm.
+//! That robot is controlled from a directional keypad (`^`, `v`, `<`, `>` and `A` to press),
+//! which is in turn operated by another robot, and so on, until the last directional keypad,
+//! which is typed on by you. The robot arms start on `A` and must never point at the gap of a keypad.
+//!
+//! The complexity of a code is the length of the shortest sequence you have to type,
+//! multiplied with the numeric part of the code.
+//! The answer is the sum of the complexities of all codes, with two robots on directional keypads.
+//!
+//! `$ day21 --file-name <file> [--robots <n>]` to execute.
+//! `$ day21 --demo` runs on the example from the puzzle description instead.
+//!
+//! The sequences are never built. Moving between two keys and pressing the second one
+//! always starts and ends with every arm further up the chain on `A`,
+//! so the cost of a move only depends on the two keys and the depth and is memoized.
+//!
+use aoc_common::{CommonArgs, Part};
+use clap::Parser;
+use std::collections::HashMap;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// How many robots operate directional keypads between you and the numeric keypad
+    #[clap(long, default_value_t = ROBOTS)]
+    robots: usize,
+}
+
+type ResultType = u64;
+
+/// A key position as `(row, column)`
+type Position = (i32, i32);
+
+const ROBOTS: usize = 2;
+
+const TOO_MANY_PRESSES: &str = "Too many presses to count, try fewer --robots";
+
+/// The example from the puzzle description, used by `--demo`
+const EXAMPLE: &str = "029A
+980A
+179A
+456A
+379A
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let content = args.common.read_input(EXAMPLE)?;
+    let codes: Vec<&str> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    let mut solver = Solver::new(args.robots);
+
+    args.common.solve(21, |part| match part {
+        Part::One => {
+            let mut total = 0;
+            for code in &codes {
+                total = solver
+                    .complexity(code)?
+                    .checked_add(total)
+                    .ok_or(TOO_MANY_PRESSES)?;
+            }
+            Ok(total)
+        }
+        Part::Two => Err(part.unsolved()),
+    })
+}
+
+/// A keypad as rows of keys, with a space marking the gap
+struct Keypad {
+    keys: HashMap<char, Position>,
+    gap: Position,
+}
+
+impl Keypad {
+    fn new(rows: &[&str]) -> Self {
+        let mut keys = HashMap::new();
+        let mut gap = (-1, -1);
+        for (row, line) in rows.iter().enumerate() {
+            for (col, key) in line.chars().enumerate() {
+                let position = (row as i32, col as i32);
+                if key == ' ' {
+                    gap = position;
+                } else {
+                    keys.insert(key, position);
+                }
+            }
+        }
+        Keypad { keys, gap }
+    }
+
+    fn numeric() -> Self {
+        Keypad::new(&["789", "456", "123", " 0A"])
+    }
+
+    fn directional() -> Self {
+        Keypad::new(&[" ^A", "<v>"])
+    }
+
+    /// The candidates for moving from one key to another and pressing it.
+    /// Zig-zagging never helps, so only all horizontal moves first or all vertical moves first
+    /// are tried, unless that order passes over the gap.
+    fn paths(&self, from: char, to: char) -> Result<Vec<String>, String> {
+        let position = |key| {
+            self.keys
+                .get(&key)
+                .copied()
+                .ok_or_else(|| format!("There is no key {:?}", key))
+        };
+        let ((from_row, from_col), (to_row, to_col)) = (position(from)?, position(to)?);
+
+        let vertical =
+            if to_row < from_row { "^" } else { "v" }.repeat(from_row.abs_diff(to_row) as usize);
+        let horizontal =
+            if to_col < from_col { "<" } else { ">" }.repeat(from_col.abs_diff(to_col) as usize);
+
+        let mut paths = Vec::new();
+        if (from_row, to_col) != self.gap {
+            paths.push(format!("{}{}A", horizontal, vertical));
+        }
+        if (to_row, from_col) != self.gap {
+            paths.push(format!("{}{}A", vertical, horizontal));
+        }
+        paths.dedup();
+        Ok(paths)
+    }
+}
+
+struct Solver {
+    numeric: Keypad,
+    directional: Keypad,
+    robots: usize,
+    /// The cost of moving from one key to another and pressing it
+    /// on a directional keypad with the given number of keypads above it
+    memo: HashMap<(char, char, usize), ResultType>,
+}
+
+impl Solver {
+    fn new(robots: usize) -> Self {
+        Solver {
+            numeric: Keypad::numeric(),
+            directional: Keypad::directional(),
+            robots,
+            memo: HashMap::new(),
+        }
+    }
+
+    /// How many presses you need for the sequence on a directional keypad
+    /// with `depth` more directional keypads above it
+    fn sequence_cost(&mut self, sequence: &str, depth: usize) -> Result<ResultType, String> {
+        if depth == 0 {
+            return Ok(sequence.len() as ResultType);
+        }
+        let mut cost = 0;
+        let mut from = 'A';
+        for to in sequence.chars() {
+            cost = self
+                .move_cost(from, to, depth)?
+                .checked_add(cost)
+                .ok_or(TOO_MANY_PRESSES)?;
+            from = to;
+        }
+        Ok(cost)
+    }
+
+    fn move_cost(&mut self, from: char, to: char, depth: usize) -> Result<ResultType, String> {
+        if let Some(cost) = self.memo.get(&(from, to, depth)) {
+            return Ok(*cost);
+        }
+        let mut best = ResultType::MAX;
+        for path in self.directional.paths(from, to)? {
+            best = best.min(self.sequence_cost(&path, depth - 1)?);
+        }
+        self.memo.insert((from, to, depth), best);
+        Ok(best)
+    }
+
+    /// The length of the shortest sequence you type for the code on the numeric keypad
+    fn code_cost(&mut self, code: &str) -> Result<ResultType, String> {
+        let mut cost = 0;
+        let mut from = 'A';
+        for to in code.chars() {
+            let mut best = ResultType::MAX;
+            for path in self.numeric.paths(from, to)? {
+                best = best.min(self.sequence_cost(&path, self.robots)?);
+            }
+            cost = best.checked_add(cost).ok_or(TOO_MANY_PRESSES)?;
+            from = to;
+        }
+        Ok(cost)
+    }
+
+    fn complexity(&mut self, code: &str) -> Result<ResultType, String> {
+        let number: ResultType = code
+            .trim_end_matches('A')
+            .parse()
+            .map_err(|_| format!("Invalid code {}", code))?;
+        Ok(self
+            .code_cost(code)?
+            .checked_mul(number)
+            .ok_or(TOO_MANY_PRESSES)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_complexities() {
+        let mut solver = Solver::new(ROBOTS);
+        assert_eq!(solver.code_cost("029A"), Ok(68));
+        let total: ResultType = EXAMPLE
+            .lines()
+            .map(|code| solver.complexity(code).unwrap())
+            .sum();
+        assert_eq!(total, 126384);
+    }
+
+    #[test]
+    fn too_many_robots() {
+        let mut solver = Solver::new(100);
+        assert_eq!(solver.code_cost("029A"), Err(TOO_MANY_PRESSES.to_string()));
+    }
+}
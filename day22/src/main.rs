@@ -0,0 +1,74 @@
+//! Day 22:
+//! Every line holds the initial secret number of a buyer.
+//! The next secret number is made in three steps, each of which mixes a value into the secret
+//! (bitwise xor) and then prunes it (modulo 16777216):
+//! mix in the secret times 64, then the secret divided by 32, then the secret times 2048.
+//! The answer is the sum of every buyer's 2000th new secret number.
+//!
+//! `$ day22 --file-name <file>` to execute.
+//! `$ day22 --demo` runs on the example from the puzzle description instead.
+//!
+use aoc_common::{CommonArgs, Part};
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
+}
+
+type Secret = u64;
+type ResultType = u64;
+
+const ITERATIONS: usize = 2000;
+
+/// 16777216 is 2^24, so pruning keeps the lowest 24 bits
+const PRUNE_MASK: Secret = (1 << 24) - 1;
+
+/// The example from the puzzle description, used by `--demo`
+const EXAMPLE: &str = "1
+10
+100
+2024
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let content = args.common.read_input(EXAMPLE)?;
+    let secrets = content
+        .split_whitespace()
+        .map(str::parse)
+        .collect::<Result<Vec<Secret>, _>>()?;
+
+    args.common.solve(22, |part| match part {
+        Part::One => Ok(sum_secrets(&secrets, ITERATIONS)),
+        Part::Two => Err(part.unsolved()),
+    })
+}
+
+/// The next secret number. Multiplying and dividing by powers of two are shifts.
+fn evolve(mut secret: Secret) -> Secret {
+    secret = (secret ^ (secret << 6)) & PRUNE_MASK;
+    secret = (secret ^ (secret >> 5)) & PRUNE_MASK;
+    (secret ^ (secret << 11)) & PRUNE_MASK
+}
+
+fn sum_secrets(secrets: &[Secret], iterations: usize) -> ResultType {
+    secrets
+        .iter()
+        .map(|secret| (0..iterations).fold(*secret, |secret, _| evolve(secret)))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_secrets() {
+        assert_eq!(evolve(123), 15887950);
+        assert_eq!(sum_secrets(&[1, 10, 100, 2024], ITERATIONS), 37327623);
+    }
+}
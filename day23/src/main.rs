@@ -0,0 +1,121 @@
+//! Day 23:
+//! Every line `ab-cd` is a connection between two computers of a LAN, in both directions.
+//! The answer is the number of sets of three computers that are all connected to each other,
+//! where at least one computer's name starts with `t`.
+//!
+//! `$ day23 --file-name <file>` to execute.
+//! `$ day23 --demo` runs on the example from the puzzle description instead.
+//!
+use aoc_common::{CommonArgs, Part};
+use clap::Parser;
+use std::collections::{BTreeSet, HashMap};
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
+}
+
+type ResultType = usize;
+
+/// The example from the puzzle description, used by `--demo`
+const EXAMPLE: &str = "kh-tc
+qp-kh
+de-cg
+ka-co
+yn-aq
+qp-ub
+cg-tb
+vc-aq
+tb-ka
+wh-tc
+yn-cg
+kh-ub
+ta-co
+de-co
+tc-td
+tb-wq
+wh-td
+ta-ka
+td-qp
+aq-cg
+wq-ub
+ub-vc
+de-ta
+wq-aq
+wq-vc
+wh-yn
+ka-de
+kh-ta
+co-tc
+wh-qp
+tb-vc
+td-yn
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let content = args.common.read_input(EXAMPLE)?;
+    let graph = Graph::parse(&content)?;
+
+    args.common.solve(23, |part| match part {
+        Part::One => Ok(count_t_triangles(&graph)),
+        Part::Two => Err(part.unsolved()),
+    })
+}
+
+/// The computers with the computers they are connected to, sorted by name
+struct Graph<'a> {
+    neighbours: HashMap<&'a str, BTreeSet<&'a str>>,
+}
+
+impl<'a> Graph<'a> {
+    fn parse(content: &'a str) -> Result<Self, String> {
+        let mut neighbours: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+        for line in content.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let (a, b) = line
+                .split_once('-')
+                .ok_or_else(|| format!("Expected a connection a-b, got {}", line))?;
+            neighbours.entry(a).or_default().insert(b);
+            neighbours.entry(b).or_default().insert(a);
+        }
+        Ok(Graph { neighbours })
+    }
+
+    /// Every set of three connected computers, each listed once in sorted order
+    fn triangles(&self) -> Vec<[&'a str; 3]> {
+        let mut triangles = Vec::new();
+        for (a, a_neighbours) in &self.neighbours {
+            for b in a_neighbours.iter().filter(|b| *b > a) {
+                for c in self.neighbours[b].iter().filter(|c| *c > b) {
+                    if a_neighbours.contains(c) {
+                        triangles.push([*a, *b, *c]);
+                    }
+                }
+            }
+        }
+        triangles
+    }
+}
+
+fn count_t_triangles(graph: &Graph) -> ResultType {
+    graph
+        .triangles()
+        .iter()
+        .filter(|triangle| triangle.iter().any(|name| name.starts_with('t')))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_triangles() {
+        let graph = Graph::parse(EXAMPLE).unwrap();
+        assert_eq!(graph.triangles().len(), 12);
+        assert_eq!(count_t_triangles(&graph), 7);
+    }
+}
@@ -0,0 +1,215 @@
+//! Day 24:
+//! The input lists the initial values of some wires (`x00: 1`), then after an empty line
+//! the gates (`x00 AND y00 -> z00`), each combining two wires with `AND`, `OR` or `XOR`
+//! into an output wire. A gate produces its output once both inputs have a value.
+//! The answer is the number formed by the bits on the wires starting with `z`,
+//! with `z00` being the least significant bit.
+//!
+//! `$ day24 --file-name <file>` to execute.
+//! `$ day24 --demo` runs on the example from the puzzle description instead.
+//!
+use aoc_common::{CommonArgs, Part};
+use clap::Parser;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
+}
+
+type ResultType = u64;
+
+/// The example from the puzzle description, used by `--demo`
+const EXAMPLE: &str = "x00: 1
+x01: 0
+x02: 1
+x03: 1
+x04: 0
+y00: 1
+y01: 1
+y02: 1
+y03: 1
+y04: 1
+
+ntg XOR fgs -> mjb
+y02 OR x01 -> tnw
+kwq OR kpj -> z05
+x00 OR x03 -> fst
+tgd XOR rvg -> z01
+vdt OR tnw -> bfw
+bfw AND frj -> z10
+ffh OR nrd -> bqk
+y00 AND y03 -> djm
+y03 OR y00 -> psh
+bqk OR frj -> z08
+tnw OR fst -> frj
+gnj AND tgd -> z11
+bfw XOR mjb -> z00
+x03 OR x00 -> vdt
+gnj AND wpb -> z02
+x04 AND y00 -> kjc
+djm OR pbm -> qhw
+nrd AND vdt -> hwm
+kjc AND fst -> rvg
+y04 OR y02 -> fgs
+y01 AND x02 -> pbm
+ntg OR kjc -> kwq
+psh XOR fgs -> tgd
+qhw XOR tgd -> z09
+pbm OR djm -> kpj
+x03 XOR y03 -> ffh
+x00 XOR y04 -> ntg
+bfw OR bqk -> z06
+nrd XOR fgs -> wpb
+frj XOR qhw -> z04
+bqk OR frj -> z07
+y03 OR x01 -> nrd
+hwm AND bqk -> z03
+tgd XOR rvg -> z12
+tnw OR pbm -> gnj
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let content = args.common.read_input(EXAMPLE)?;
+    let circuit = Circuit::parse(&content)?;
+
+    args.common.solve(24, |part| match part {
+        Part::One => Ok(circuit.output()?),
+        Part::Two => Err(part.unsolved()),
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operation {
+    And,
+    Or,
+    Xor,
+}
+
+impl Operation {
+    fn apply(self, left: bool, right: bool) -> bool {
+        match self {
+            Operation::And => left && right,
+            Operation::Or => left || right,
+            Operation::Xor => left ^ right,
+        }
+    }
+}
+
+struct Gate<'a> {
+    left: &'a str,
+    operation: Operation,
+    right: &'a str,
+}
+
+struct Circuit<'a> {
+    initial: HashMap<&'a str, bool>,
+    /// The gates by the wire they write to
+    gates: HashMap<&'a str, Gate<'a>>,
+}
+
+impl<'a> Circuit<'a> {
+    fn parse(content: &'a str) -> Result<Self, String> {
+        let mut initial = HashMap::new();
+        let mut gates = HashMap::new();
+        for line in content.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            if let Some((wire, value)) = line.split_once(": ") {
+                let value = match value {
+                    "0" => false,
+                    "1" => true,
+                    _ => return Err(format!("Invalid wire value {}", line)),
+                };
+                initial.insert(wire, value);
+                continue;
+            }
+
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let [left, operation, right, "->", output] = parts[..] else {
+                return Err(format!("Invalid gate {}", line));
+            };
+            let operation = match operation {
+                "AND" => Operation::And,
+                "OR" => Operation::Or,
+                "XOR" => Operation::Xor,
+                _ => return Err(format!("Unknown operation {}", operation)),
+            };
+            let gate = Gate {
+                left,
+                operation,
+                right,
+            };
+            if gates.insert(output, gate).is_some() {
+                return Err(format!("Wire {} is the output of two gates", output));
+            }
+        }
+        Ok(Circuit { initial, gates })
+    }
+
+    /// Evaluates a wire, looking up the gates it depends on as needed.
+    /// `active` holds the wires currently being evaluated, to detect loops.
+    fn evaluate(
+        &self,
+        wire: &'a str,
+        values: &mut HashMap<&'a str, bool>,
+        active: &mut HashSet<&'a str>,
+    ) -> Result<bool, String> {
+        if let Some(value) = values.get(wire) {
+            return Ok(*value);
+        }
+        let gate = self
+            .gates
+            .get(wire)
+            .ok_or_else(|| format!("Wire {} has no value and no gate", wire))?;
+        if !active.insert(wire) {
+            return Err(format!("Wire {} depends on itself", wire));
+        }
+        let left = self.evaluate(gate.left, values, active)?;
+        let right = self.evaluate(gate.right, values, active)?;
+        active.remove(wire);
+
+        let value = gate.operation.apply(left, right);
+        values.insert(wire, value);
+        Ok(value)
+    }
+
+    /// The number on the `z` wires, where the digits after the `z` give the bit
+    fn output(&self) -> Result<ResultType, String> {
+        let z_wires: HashSet<&str> = self
+            .gates
+            .keys()
+            .chain(self.initial.keys())
+            .filter(|wire| wire.starts_with('z'))
+            .copied()
+            .collect();
+
+        let mut values = self.initial.clone();
+        let mut active = HashSet::new();
+        let mut number = 0;
+        for wire in z_wires {
+            let bit: u32 = wire[1..]
+                .parse()
+                .map_err(|_| format!("Wire {} has no bit number", wire))?;
+            if bit >= ResultType::BITS {
+                return Err(format!("Wire {} does not fit into the result", wire));
+            }
+            if self.evaluate(wire, &mut values, &mut active)? {
+                number |= 1 << bit;
+            }
+        }
+        Ok(number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_output() {
+        assert_eq!(Circuit::parse(EXAMPLE).unwrap().output(), Ok(2024));
+    }
+}
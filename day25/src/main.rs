@@ -0,0 +1,162 @@
+//! Day 25:
+//! The input is a list of schematics separated by empty lines, each 7 rows of 5 columns.
+//! Locks have their top row filled with `#` and pins reaching down,
+//! keys have their bottom row filled and shapes reaching up.
+//! Both are described by the height of every column, not counting the filled row.
+//! A key fits a lock if no column of the two overlaps, i.e. the heights add up to at most 5.
+//! The answer is the number of lock/key pairs that fit.
+//!
+//! `$ day25 --file-name <file>` to execute.
+//! `$ day25 --demo` runs on the example from the puzzle description instead.
+//!
+use aoc_common::{CommonArgs, Part};
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
+}
+
+type ResultType = usize;
+
+const COLUMNS: usize = 5;
+const ROWS: usize = 7;
+
+/// The space inside a schematic, without the filled top or bottom row
+const SPACE: u8 = 5;
+
+/// The height of every column
+type Heights = [u8; COLUMNS];
+
+/// The example from the puzzle description, used by `--demo`
+const EXAMPLE: &str = "#####
+.####
+.####
+.####
+.#.#.
+.#...
+.....
+
+#####
+##.##
+.#.##
+...##
+...#.
+...#.
+.....
+
+.....
+#....
+#....
+#...#
+#.#.#
+#.###
+#####
+
+.....
+.....
+#.#..
+###..
+###.#
+###.#
+#####
+
+.....
+.....
+.....
+#....
+#.#..
+#.#.#
+#####
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let content = args.common.read_input(EXAMPLE)?;
+    let (locks, keys) = parse(&content)?;
+
+    args.common.solve(25, |part| match part {
+        Part::One => Ok(count_fitting(&locks, &keys)),
+        Part::Two => Err(part.unsolved()),
+    })
+}
+
+/// Reads the schematics into the heights of the locks and the keys
+fn parse(content: &str) -> Result<(Vec<Heights>, Vec<Heights>), String> {
+    let mut locks = Vec::new();
+    let mut keys = Vec::new();
+    let content = content.replace("\r\n", "\n");
+    for block in content.split("\n\n").filter(|b| !b.trim().is_empty()) {
+        let rows: Vec<&str> = block.lines().map(str::trim).collect();
+        let well_formed = rows.len() == ROWS
+            && rows
+                .iter()
+                .all(|row| row.len() == COLUMNS && row.chars().all(|c| c == '#' || c == '.'));
+        if !well_formed {
+            return Err(format!(
+                "Schematic is not {} rows of {} `#` or `.`:\n{}",
+                ROWS, COLUMNS, block
+            ));
+        }
+        let mut heights = [0; COLUMNS];
+        // Counting every `#` includes the filled row, which the heights leave out.
+        for row in &rows {
+            for (column, c) in row.chars().enumerate() {
+                if c == '#' {
+                    heights[column] += 1;
+                }
+            }
+        }
+        let heights = heights.map(|height: u8| height.saturating_sub(1));
+        if rows
+            .first()
+            .is_some_and(|row| row.chars().all(|c| c == '#'))
+        {
+            locks.push(heights);
+        } else if rows.last().is_some_and(|row| row.chars().all(|c| c == '#')) {
+            keys.push(heights);
+        } else {
+            return Err(format!("Schematic is neither a lock nor a key:\n{}", block));
+        }
+    }
+    Ok((locks, keys))
+}
+
+fn fits(lock: &Heights, key: &Heights) -> bool {
+    lock.iter().zip(key).all(|(lock, key)| lock + key <= SPACE)
+}
+
+fn count_fitting(locks: &[Heights], keys: &[Heights]) -> ResultType {
+    locks
+        .iter()
+        .map(|lock| keys.iter().filter(|key| fits(lock, key)).count())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_fitting_pairs() {
+        let (locks, keys) = parse(EXAMPLE).unwrap();
+        assert_eq!(locks[0], [0, 5, 3, 4, 3]);
+        assert_eq!(keys[0], [5, 0, 2, 1, 3]);
+        assert_eq!(count_fitting(&locks, &keys), 3);
+    }
+
+    #[test]
+    fn malformed_schematics() {
+        let lock = "#####\n.####\n.####\n.####\n.#.#.\n.#...\n.....";
+        assert!(parse(lock).is_ok());
+        // An extra column
+        assert!(parse(&lock.replace(".....", "......")).is_err());
+        // An extra row
+        assert!(parse(&format!("{}\n.....", lock)).is_err());
+        // A blank line at the start of the schematic
+        assert!(parse(&format!("{}\n\n\n{}", lock, lock)).is_err());
+    }
+}
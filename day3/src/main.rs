@@ -7,23 +7,48 @@
 //! - Task 2:
 //!   There are also `do()` and `don't()` string in text.
 //!   Everytime a `don't()` appears, discard all pairs until you find a `do()`.
-//!   `$ day3 --file-name <file> -c|--conditionals` to execute.
+//!   `$ day3 --file-name <file> -c|--conditionals` or `--part 2` to execute.
+//!
+//! Pass `--demo` instead of a file to run on the example from the puzzle description of the task.
+//! Pass `--explain` to print every instruction with the running total (meant for small inputs).
+//! The flags for the output, like `--quiet` or `--output kv`, are the same for every day.
+//!
+//! An input that arrives in pieces can be processed one piece per run:
+//! `--save-state <file>` stores the total and whether multiplications are enabled after this run,
+//! `--load-state <file>` continues from a stored state instead of starting at 0 and enabled.
+//! Only one part can be solved when saving the state.
+//! The pieces have to be split between instructions, not in the middle of one.
 //!
 //! **Note**: As three-digit numbers fit into `u16` but not `u8`. I have chosen `u16` as the container.
 //! When building the sum, they get upcast to `u64`, which will remain the presentation until the very end.
 //! Thus, the result cannot exceed [`u64::MAX`].
 //!
+use aoc_common::{CommonArgs, Part};
 use clap::Parser;
-use logos::{Lexer, Logos, Source};
-use std::path::PathBuf;
+use iterator_utils::Toggleable;
+use logos::{Lexer, Logos};
+use std::path::{Path, PathBuf};
 
 #[derive(Parser, Debug)]
 pub struct Args {
-    #[clap(short, long)]
-    file_name: PathBuf,
+    #[command(flatten)]
+    common: CommonArgs,
 
-    #[clap(short, long, default_value = "false")]
+    /// Respect `do()` and `don't()`, the same as `--part 2`
+    #[clap(short, long, default_value = "false", conflicts_with_all = ["part", "both"])]
     conditionals: bool,
+
+    /// Print every instruction with the running total
+    #[clap(long, default_value = "false", conflicts_with_all = ["quiet", "output"])]
+    explain: bool,
+
+    /// Continue from the state stored by an earlier run
+    #[clap(long)]
+    load_state: Option<PathBuf>,
+
+    /// Store the state after this run, so a later run can continue from it
+    #[clap(long, conflicts_with = "both")]
+    save_state: Option<PathBuf>,
 }
 
 /// This enum represents the things we are looking for in the text.
@@ -54,140 +79,218 @@ pub fn mul_callback(lex: &mut Lexer<Token>) -> (u16, u16) {
 
 type Acc = u64;
 
-fn main() -> std::io::Result<()> {
-    let args = Args::parse();
+/// Everything a run needs to continue where an earlier one stopped
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct State {
+    total: Acc,
+    enabled: bool,
+}
 
-    println!("Reading file: {}", args.file_name.display());
-    println!(
-        "{} conditionals",
-        if args.conditionals {
-            "Respecting"
-        } else {
-            "Ignoring"
+impl Default for State {
+    fn default() -> Self {
+        State {
+            total: 0,
+            enabled: true,
         }
-    );
-
-    let content = std::fs::read_to_string(args.file_name)?;
-
-    let lex = Token::lexer(&content);
-
-    let result: Acc = {
-        let tokens = lex.filter_map(|t| t.ok());
-        if args.conditionals {
-            tokens
-                .toggle_on(|t| *t == Token::Do, |t| *t == Token::Dont)
-                .filter_map(|t| match t {
-                    Token::Mul(tuple) => Some(tuple),
-                    _ => None,
-                })
-                .map(|(l, r)| l as Acc * r as Acc)
-                .sum()
-        } else {
-            tokens
-                .filter_map(|t| match t {
-                    Token::Mul(tuple) => Some(tuple),
-                    _ => None,
-                })
-                .map(|(l, r)| l as Acc * r as Acc)
-                .sum()
+    }
+}
+
+impl State {
+    /// Reads a state in the format written by [`State::save`]
+    fn load(path: &Path) -> std::io::Result<Self> {
+        let invalid =
+            |message: String| std::io::Error::new(std::io::ErrorKind::InvalidData, message);
+        let content = std::fs::read_to_string(path)?;
+        let mut state = State::default();
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            match line.trim().split_once('=') {
+                Some(("total", value)) => {
+                    state.total = value
+                        .parse()
+                        .map_err(|e| invalid(format!("Invalid total: {}", e)))?
+                }
+                Some(("enabled", value)) => {
+                    state.enabled = value
+                        .parse()
+                        .map_err(|e| invalid(format!("Invalid enabled: {}", e)))?
+                }
+                _ => return Err(invalid(format!("Unexpected line in state file: {}", line))),
+            }
         }
+        Ok(state)
+    }
+
+    fn save(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(
+            path,
+            format!("total={}\nenabled={}\n", self.total, self.enabled),
+        )
+    }
+}
+
+/// The examples from the puzzle description of both tasks, used by `--demo`
+const EXAMPLE: &str = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+const CONDITIONALS_EXAMPLE: &str =
+    "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = Args::parse();
+    if args.conditionals {
+        args.common.part = Part::Two;
+    }
+
+    let state = match &args.load_state {
+        Some(path) => State::load(path)?,
+        None => State::default(),
     };
 
-    println!("{}", result);
+    // The conditionals example gives the same answer for part 1 as the other one
+    let example = if args.common.parts().contains(&Part::Two) {
+        CONDITIONALS_EXAMPLE
+    } else {
+        EXAMPLE
+    };
+    let content = args.common.read_input(example)?;
+    let tokens = tokenize(&content);
+
+    let mut next_state = state;
+    args.common.solve(3, |part| {
+        let conditionals = part == Part::Two;
+        if args.explain {
+            for line in explain(&tokens, conditionals, state) {
+                println!("{}", line);
+            }
+        }
+
+        next_state = State {
+            total: state.total + sum_products(&tokens, conditionals, state.enabled),
+            enabled: enabled_after(&tokens, state.enabled),
+        };
+        Ok(next_state.total)
+    })?;
+
+    if let Some(path) = &args.save_state {
+        next_state.save(path)?;
+    }
 
     Ok(())
 }
 
-pub struct Toggle<J, I: Iterator<Item = J>, POn: FnMut(&J) -> bool, POff: FnMut(&J) -> bool> {
-    iter: I,
-    on_function: POn,
-    off_function: POff,
-    state: bool,
+/// Finds all instructions in the text, skipping everything else
+fn tokenize(content: &str) -> Vec<Token> {
+    Token::lexer(content).filter_map(|t| t.ok()).collect()
 }
 
-impl<J, I: Iterator<Item = J>, POn: FnMut(&J) -> bool, POff: FnMut(&J) -> bool>
-    Toggle<J, I, POn, POff>
-{
-    pub fn new(iter: I, on_function: POn, off_function: POff, initial_state: bool) -> Self {
-        Toggle {
-            iter,
-            on_function,
-            off_function,
-            state: initial_state,
-        }
-    }
-
-    pub fn new_on(iter: I, on_function: POn, off_function: POff) -> Self {
-        Self::new(iter, on_function, off_function, true)
+/// Sums the products of all multiplication instructions.
+/// With conditionals, the ones between a `don't()` and the next `do()` are skipped,
+/// and `enabled` says whether multiplications count before the first `do()` or `don't()`.
+fn sum_products(tokens: &[Token], conditionals: bool, enabled: bool) -> Acc {
+    let tokens = tokens.iter();
+    if conditionals {
+        tokens
+            .toggle(|t| **t == Token::Do, |t| **t == Token::Dont, enabled)
+            .filter_map(|t| match t {
+                Token::Mul(tuple) => Some(tuple),
+                _ => None,
+            })
+            .map(|(l, r)| *l as Acc * *r as Acc)
+            .sum()
+    } else {
+        tokens
+            .filter_map(|t| match t {
+                Token::Mul(tuple) => Some(tuple),
+                _ => None,
+            })
+            .map(|(l, r)| *l as Acc * *r as Acc)
+            .sum()
     }
+}
 
-    pub fn new_off(iter: I, on_function: POn, off_function: POff) -> Self {
-        Self::new(iter, on_function, off_function, false)
-    }
+/// Whether multiplications are enabled after the tokens, if they were `enabled` before
+fn enabled_after(tokens: &[Token], enabled: bool) -> bool {
+    tokens
+        .iter()
+        .rev()
+        .find_map(|t| match t {
+            Token::Do => Some(true),
+            Token::Dont => Some(false),
+            Token::Mul(_) => None,
+        })
+        .unwrap_or(enabled)
 }
 
-/// An Iterator that lets you discard large chunks of data according to some toggle-rules.
-/// When getting the next element, this iterator gets one from the underlying iterator,
-/// then checks if the new element changes its state.
-/// After that, if the current state is on, the element is returned.
-/// If the state is on, the procedure is repeated until an element is found that turns its state on.
-///
-/// The element that turns a state on will be returned.
-/// The element that turns a state off will not be returned.
-///
-/// While the state is on, the on-function will not be queried.
-/// While the state is off, the off-function will not be queried.
-impl<J, I: Iterator<Item = J>, POn: FnMut(&J) -> bool, POff: FnMut(&J) -> bool> Iterator
-    for Toggle<J, I, POn, POff>
-{
-    type Item = J;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        while let Some(v) = self.iter.next() {
-            if self.state {
-                if (self.off_function)(&v) {
-                    self.state = false;
-                }
-            } else {
-                if (self.on_function)(&v) {
-                    self.state = true;
-                }
+/// Describes every instruction in order with the running total, one line per instruction.
+/// Without conditionals, `do()` and `don't()` are left out as they have no effect.
+fn explain(tokens: &[Token], conditionals: bool, state: State) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut enabled = state.enabled || !conditionals;
+    let mut total = state.total;
+    for token in tokens {
+        match token {
+            Token::Mul((l, r)) if enabled => {
+                total += *l as Acc * *r as Acc;
+                lines.push(format!(
+                    "mul({},{}) = {} -> total {}",
+                    l,
+                    r,
+                    *l as Acc * *r as Acc,
+                    total
+                ));
+            }
+            Token::Mul((l, r)) => lines.push(format!("mul({},{}) skipped", l, r)),
+            Token::Do if conditionals => {
+                enabled = true;
+                lines.push("do()".to_string());
             }
-            if self.state {
-                return Some(v);
+            Token::Dont if conditionals => {
+                enabled = false;
+                lines.push("don't()".to_string());
             }
+            _ => {}
         }
-        None
     }
+    lines
 }
-trait Toggleable<J, I: Iterator<Item = J>, POn: FnMut(&J) -> bool, POff: FnMut(&J) -> bool>:
-    Iterator<Item = J> + Sized
-{
-    fn toggle_on(self, on_function: POn, off_function: POff) -> Toggle<J, I, POn, POff>;
-    fn toggle_off(self, on_function: POn, off_function: POff) -> Toggle<J, I, POn, POff>;
-    fn toggle(
-        self,
-        on_function: POn,
-        off_function: POff,
-        initial_state: bool,
-    ) -> Toggle<J, I, POn, POff>;
-}
 
-impl<J, I: Iterator<Item = J>, POn: FnMut(&J) -> bool, POff: FnMut(&J) -> bool>
-    Toggleable<J, Self, POn, POff> for I
-{
-    fn toggle_on(self, on_function: POn, off_function: POff) -> Toggle<J, I, POn, POff> {
-        Toggle::new_on(self, on_function, off_function)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_without_conditionals() {
+        assert_eq!(sum_products(&tokenize(EXAMPLE), false, true), 161);
+    }
+
+    #[test]
+    fn example_with_conditionals() {
+        assert_eq!(
+            sum_products(&tokenize(CONDITIONALS_EXAMPLE), true, true),
+            48
+        );
     }
-    fn toggle_off(self, on_function: POn, off_function: POff) -> Toggle<J, I, POn, POff> {
-        Toggle::new_off(self, on_function, off_function)
+
+    #[test]
+    fn example_with_conditionals_in_pieces() {
+        let (first, second) =
+            CONDITIONALS_EXAMPLE.split_at(CONDITIONALS_EXAMPLE.find("_mul").unwrap());
+        let (first, second) = (tokenize(first), tokenize(second));
+        let total = sum_products(&first, true, true);
+        let enabled = enabled_after(&first, true);
+        assert!(!enabled);
+        assert_eq!(total + sum_products(&second, true, enabled), 48);
     }
-    fn toggle(
-        self,
-        on_function: POn,
-        off_function: POff,
-        initial_state: bool,
-    ) -> Toggle<J, I, POn, POff> {
-        Toggle::new(self, on_function, off_function, initial_state)
+
+    #[test]
+    fn example_explain_without_conditionals() {
+        insta::assert_snapshot!(
+            explain(&tokenize(CONDITIONALS_EXAMPLE), false, State::default()).join("\n")
+        );
+    }
+
+    #[test]
+    fn example_explain_with_conditionals() {
+        insta::assert_snapshot!(
+            explain(&tokenize(CONDITIONALS_EXAMPLE), true, State::default()).join("\n")
+        );
     }
 }
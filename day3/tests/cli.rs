@@ -0,0 +1,90 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+fn day3() -> Command {
+    Command::cargo_bin("day3").unwrap()
+}
+
+#[test]
+fn quiet_without_conditionals() {
+    day3()
+        .args(["--file-name", "tests/example.txt", "--quiet"])
+        .assert()
+        .success()
+        .stdout("161\n");
+}
+
+#[test]
+fn quiet_with_conditionals() {
+    day3()
+        .args([
+            "--file-name",
+            "tests/example_conditionals.txt",
+            "--conditionals",
+            "--quiet",
+        ])
+        .assert()
+        .success()
+        .stdout("48\n");
+}
+
+#[test]
+fn kv_output() {
+    day3()
+        .args([
+            "--file-name",
+            "tests/example_conditionals.txt",
+            "--conditionals",
+            "--output",
+            "kv",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "day=3 part=2 answer=48 parse_us=",
+        ));
+}
+
+#[test]
+fn part_two_from_stdin() {
+    day3()
+        .args(["--file-name", "-", "--part", "2", "--quiet"])
+        .write_stdin(std::fs::read_to_string("tests/example_conditionals.txt").unwrap())
+        .assert()
+        .success()
+        .stdout("48\n");
+}
+
+#[test]
+fn demo_with_conditionals() {
+    day3()
+        .args(["--demo", "--conditionals", "--quiet"])
+        .assert()
+        .success()
+        .stdout("48\n");
+}
+
+#[test]
+fn missing_file() {
+    day3()
+        .args(["--file-name", "tests/does_not_exist.txt"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::starts_with("Error:"));
+}
+
+#[test]
+fn malformed_state() {
+    day3()
+        .args([
+            "--file-name",
+            "tests/example.txt",
+            "--load-state",
+            "tests/malformed_state.txt",
+            "--quiet",
+        ])
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(predicate::str::contains("Invalid enabled"));
+}
@@ -0,0 +1,130 @@
+//! Day 4:
+//! Find every `XMAS` in a grid of letters.
+//! The word may be written horizontally, vertically or diagonally, forwards or backwards,
+//! and occurrences may overlap.
+//!
+//! Part 2: Find every `MAS` written twice diagonally in the shape of an X, crossing at the `A`.
+//! Each `MAS` may be written forwards or backwards.
+//!
+//! `$ day4 --file-name <file> [--part 2]` to execute.
+//! `$ day4 --demo` runs on the example from the puzzle description instead.
+//!
+use aoc_common::grid::{Grid, Position};
+use aoc_common::{CommonArgs, Part};
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
+}
+
+type ResultType = usize;
+
+/// The word we are looking for
+const WORD: &[u8] = b"XMAS";
+
+/// The word crossing itself in part 2
+const CROSS_WORD: &[u8] = b"MAS";
+
+/// All eight directions a word can be written in, as `(row, column)` steps
+const DIRECTIONS: [Position; 8] = [
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+];
+
+/// The example from the puzzle description, used by `--demo`
+const EXAMPLE: &str = "MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let content = args.common.read_input(EXAMPLE)?;
+    let grid = parse(&content)?;
+
+    args.common.solve(4, |part| match part {
+        Part::One => Ok(count_word(&grid, WORD)),
+        Part::Two => Ok(count_crosses(&grid, CROSS_WORD)),
+    })
+}
+
+/// The letters of the puzzle, stored as bytes as the input only consists of ASCII letters
+type Letters = Grid<u8>;
+
+fn parse(content: &str) -> Result<Letters, String> {
+    Grid::parse(content, |_, c| c.is_ascii().then_some(c as u8))
+}
+
+/// Checks if the word starts at the position and continues in the direction
+fn matches(grid: &Letters, word: &[u8], (row, col): Position, (d_row, d_col): Position) -> bool {
+    word.iter().enumerate().all(|(i, letter)| {
+        grid.get((row + d_row * i as isize, col + d_col * i as isize)) == Some(letter)
+    })
+}
+
+/// Counts the occurrences of the word in every direction
+fn count_word(grid: &Letters, word: &[u8]) -> ResultType {
+    grid.positions()
+        .map(|position| {
+            DIRECTIONS
+                .iter()
+                .filter(|direction| matches(grid, word, position, **direction))
+                .count()
+        })
+        .sum()
+}
+
+/// Counts the positions where the word crosses itself on both diagonals.
+/// The word needs an odd length, so that its middle letter is where the diagonals cross.
+fn count_crosses(grid: &Letters, word: &[u8]) -> ResultType {
+    let half = (word.len() / 2) as isize;
+    // A diagonal through the centre, read in either direction
+    let on_diagonal = |(row, col): Position, (d_row, d_col): Position| {
+        matches(
+            grid,
+            word,
+            (row - d_row * half, col - d_col * half),
+            (d_row, d_col),
+        ) || matches(
+            grid,
+            word,
+            (row + d_row * half, col + d_col * half),
+            (-d_row, -d_col),
+        )
+    };
+    grid.positions()
+        .filter(|centre| on_diagonal(*centre, (1, 1)) && on_diagonal(*centre, (1, -1)))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_xmas() {
+        assert_eq!(count_word(&parse(EXAMPLE).unwrap(), WORD), 18);
+    }
+
+    #[test]
+    fn example_x_mas() {
+        assert_eq!(count_crosses(&parse(EXAMPLE).unwrap(), CROSS_WORD), 9);
+    }
+}
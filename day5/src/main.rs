@@ -0,0 +1,173 @@
+//! Day 5:
+//! The input consists of two sections separated by a blank line.
+//! The first section holds ordering rules `X|Y`, meaning page `X` has to be printed before page `Y`.
+//! The second section holds updates, comma-separated lists of pages.
+//!
+//! An update is correctly ordered if no rule is violated by any two of its pages.
+//! Rules about pages that are not part of an update are ignored.
+//! The answer is the sum of the middle pages of all correctly ordered updates.
+//!
+//! Part 2: The incorrectly ordered updates are put in the right order according to the rules.
+//! The answer is the sum of their middle pages.
+//!
+//! `$ day5 --file-name <file> [--part 2]` to execute.
+//! `$ day5 --demo` runs on the example from the puzzle description instead.
+//!
+use aoc_common::{CommonArgs, Part};
+use clap::Parser;
+use std::collections::HashSet;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
+}
+
+type Page = u32;
+type ResultType = u32;
+
+/// All `X|Y` rules, stored as `(X, Y)`
+type Rules = HashSet<(Page, Page)>;
+type Update = Vec<Page>;
+
+/// The example from the puzzle description, used by `--demo`
+const EXAMPLE: &str = "47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let content = args.common.read_input(EXAMPLE)?;
+    let (rules, updates) = parse(&content)?;
+
+    args.common.solve(5, |part| match part {
+        Part::One => Ok(sum_correct_middle_pages(&rules, &updates)),
+        Part::Two => Ok(sum_reordered_middle_pages(&rules, &updates)?),
+    })
+}
+
+/// Reads the rule section up to the first blank line and the update section after it
+fn parse(content: &str) -> Result<(Rules, Vec<Update>), Box<dyn std::error::Error>> {
+    let mut lines = content.lines().map(str::trim);
+
+    let mut rules = Rules::new();
+    for line in lines.by_ref().take_while(|line| !line.is_empty()) {
+        let (before, after) = line
+            .split_once('|')
+            .ok_or_else(|| format!("Invalid rule: {}", line))?;
+        rules.insert((before.parse()?, after.parse()?));
+    }
+
+    let mut updates = Vec::new();
+    for line in lines.filter(|line| !line.is_empty()) {
+        updates.push(
+            line.split(',')
+                .map(|page| page.parse())
+                .collect::<Result<Update, _>>()?,
+        );
+    }
+
+    Ok((rules, updates))
+}
+
+/// Checks that no later page of the update has to be printed before an earlier one
+fn is_ordered(rules: &Rules, update: &[Page]) -> bool {
+    update.iter().enumerate().all(|(i, before)| {
+        update[i + 1..]
+            .iter()
+            .all(|after| !rules.contains(&(*after, *before)))
+    })
+}
+
+fn sum_correct_middle_pages(rules: &Rules, updates: &[Update]) -> ResultType {
+    updates
+        .iter()
+        .filter(|update| is_ordered(rules, update))
+        .map(|update| update[update.len() / 2])
+        .sum()
+}
+
+/// Sorts the pages topologically: repeatedly takes the first remaining page
+/// that no rule requires another remaining page to precede.
+/// Pages without rules between them keep their relative order.
+/// Fails if the rules about the pages form a cycle.
+fn reorder(rules: &Rules, update: &[Page]) -> Result<Update, String> {
+    let mut remaining = update.to_vec();
+    let mut ordered = Vec::with_capacity(update.len());
+    while !remaining.is_empty() {
+        let next = remaining
+            .iter()
+            .position(|page| {
+                !remaining
+                    .iter()
+                    .any(|other| rules.contains(&(*other, *page)))
+            })
+            .ok_or_else(|| format!("The rules for the update {:?} form a cycle", update))?;
+        ordered.push(remaining.remove(next));
+    }
+    Ok(ordered)
+}
+
+fn sum_reordered_middle_pages(rules: &Rules, updates: &[Update]) -> Result<ResultType, String> {
+    let mut sum = 0;
+    for update in updates.iter().filter(|update| !is_ordered(rules, update)) {
+        let update = reorder(rules, update)?;
+        sum += update[update.len() / 2];
+    }
+    Ok(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_correct_middle_pages() {
+        let (rules, updates) = parse(EXAMPLE).unwrap();
+        assert_eq!(sum_correct_middle_pages(&rules, &updates), 143);
+    }
+
+    #[test]
+    fn example_reordered_middle_pages() {
+        let (rules, updates) = parse(EXAMPLE).unwrap();
+        assert_eq!(reorder(&rules, &updates[3]).unwrap(), [97, 75, 47, 61, 53]);
+        assert_eq!(sum_reordered_middle_pages(&rules, &updates), Ok(123));
+    }
+
+    #[test]
+    fn incomplete_and_cyclic_rules() {
+        let rules = Rules::from([(1, 3)]);
+        assert_eq!(reorder(&rules, &[3, 2, 1]), Ok(vec![2, 1, 3]));
+
+        let rules = Rules::from([(1, 2), (2, 3), (3, 1)]);
+        assert!(reorder(&rules, &[1, 2, 3]).is_err());
+    }
+}
@@ -0,0 +1,304 @@
+//! Day 6:
+//! A guard patrols a lab. The map marks obstacles with `#` and the guard with `^`.
+//! The guard starts facing up, walks straight ahead and turns right by 90 degrees
+//! whenever an obstacle is directly in front, until they leave the map.
+//! The answer is the number of distinct positions the guard visits, including the start.
+//!
+//! Part 2: A single new obstacle can be placed anywhere except on the starting position.
+//! The answer is the number of positions where it makes the guard walk in a loop forever.
+//! Only positions on the original path can change the walk, and they are tried in parallel,
+//! on as many threads as `--jobs` allows.
+//!
+//! `$ day6 --file-name <file> [--part 2]` to execute.
+//! `$ day6 --demo` runs on the example from the puzzle description instead.
+//! `$ day6 --file-name <file> --trace <trace_file>` also writes every step of the guard
+//! as CSV with the columns `step,x,y,direction`, where `x` is the column and `y` the row.
+//!
+use aoc_common::grid::{Grid, Position};
+use aoc_common::{CommonArgs, Part};
+use clap::Parser;
+use rayon::prelude::*;
+use std::fmt;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// Writes every step of the guard as CSV to this file
+    #[clap(long)]
+    trace: Option<PathBuf>,
+}
+
+type ResultType = usize;
+
+const NEVER_LEAVES: &str = "The guard never leaves the map";
+
+/// The example from the puzzle description, used by `--demo`
+const EXAMPLE: &str = "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let content = args.common.read_input(EXAMPLE)?;
+    let (grid, guard) = parse(&content)?;
+
+    if let Some(trace) = &args.trace {
+        let mut writer = BufWriter::new(std::fs::File::create(trace)?);
+        write_trace(&grid, guard, &mut writer)?;
+        writer.flush()?;
+    }
+
+    args.common.solve(6, |part| match part {
+        Part::One => Ok(count_visited(&grid, guard)?),
+        Part::Two => {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(args.common.jobs.unwrap_or(0))
+                .build()?;
+            Ok(pool.install(|| count_loop_obstacles(&grid, guard))?)
+        }
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Cell {
+    Free,
+    Obstacle,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    /// The position one step from the given one, which may be off the map
+    fn from(self, (row, col): Position) -> Position {
+        match self {
+            Direction::Up => (row - 1, col),
+            Direction::Right => (row, col + 1),
+            Direction::Down => (row + 1, col),
+            Direction::Left => (row, col - 1),
+        }
+    }
+
+    /// A distinct bit per direction, to store several directions in one byte
+    fn bit(self) -> u8 {
+        match self {
+            Direction::Up => 1,
+            Direction::Right => 2,
+            Direction::Down => 4,
+            Direction::Left => 8,
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Direction::Up => "up",
+            Direction::Right => "right",
+            Direction::Down => "down",
+            Direction::Left => "left",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Guard {
+    position: Position,
+    direction: Direction,
+}
+
+impl Guard {
+    /// Either walks one step forward or turns right if an obstacle is in front.
+    /// Returns `false` if the guard walked off the map.
+    fn step(&mut self, grid: &Grid<Cell>) -> bool {
+        let next = self.direction.from(self.position);
+        match grid.get(next) {
+            None => false,
+            Some(Cell::Obstacle) => {
+                self.direction = self.direction.turn_right();
+                true
+            }
+            Some(Cell::Free) => {
+                self.position = next;
+                true
+            }
+        }
+    }
+}
+
+/// Reads the map and the starting position of the guard
+fn parse(content: &str) -> Result<(Grid<Cell>, Guard), Box<dyn std::error::Error>> {
+    let mut guard = None;
+    let grid = Grid::parse(content, |position, c| match c {
+        '.' => Some(Cell::Free),
+        '#' => Some(Cell::Obstacle),
+        '^' => {
+            guard = Some(Guard {
+                position,
+                direction: Direction::Up,
+            });
+            Some(Cell::Free)
+        }
+        _ => None,
+    })?;
+
+    Ok((grid, guard.ok_or("No guard on the map")?))
+}
+
+/// Notes the direction the guard faces at their position.
+/// Returns `false` if the guard was there in that direction before, i.e. walks in a loop.
+fn record(seen: &mut Grid<u8>, guard: Guard) -> bool {
+    let directions = &mut seen[guard.position];
+    let new = *directions & guard.direction.bit() == 0;
+    *directions |= guard.direction.bit();
+    new
+}
+
+/// Walks the guard until they leave the map and marks the positions on the way.
+/// Fails if the guard walks in a loop instead.
+fn visited(grid: &Grid<Cell>, mut guard: Guard) -> Result<Grid<bool>, String> {
+    let mut seen = Grid::new(grid.width(), grid.height(), 0u8);
+    let mut visited = Grid::new(grid.width(), grid.height(), false);
+    loop {
+        if !record(&mut seen, guard) {
+            return Err(NEVER_LEAVES.to_string());
+        }
+        visited[guard.position] = true;
+        if !guard.step(grid) {
+            return Ok(visited);
+        }
+    }
+}
+
+fn count_visited(grid: &Grid<Cell>, guard: Guard) -> Result<ResultType, String> {
+    Ok(visited(grid, guard)?.cells().iter().filter(|v| **v).count())
+}
+
+/// Walks the guard until they leave the map or come back to a position in a direction seen before
+fn is_loop(grid: &Grid<Cell>, mut guard: Guard) -> bool {
+    let mut seen = Grid::new(grid.width(), grid.height(), 0u8);
+    loop {
+        if !record(&mut seen, guard) {
+            return true;
+        }
+        if !guard.step(grid) {
+            return false;
+        }
+    }
+}
+
+/// Counts the positions where a new obstacle traps the guard in a loop
+fn count_loop_obstacles(grid: &Grid<Cell>, guard: Guard) -> Result<ResultType, String> {
+    let path = visited(grid, guard)?;
+    let candidates: Vec<Position> = grid
+        .positions()
+        .filter(|position| path[*position] && *position != guard.position)
+        .collect();
+
+    Ok(candidates
+        .par_iter()
+        .filter(|obstacle| {
+            let mut blocked = grid.clone();
+            blocked[**obstacle] = Cell::Obstacle;
+            is_loop(&blocked, guard)
+        })
+        .count())
+}
+
+/// Writes the state of the guard after every step as CSV, starting with the initial state as step 0.
+/// Turning counts as a step, so consecutive records may share a position.
+/// Fails once the guard is back in a state already written, as the trace would never end.
+fn write_trace(
+    grid: &Grid<Cell>,
+    mut guard: Guard,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn std::error::Error>> {
+    writeln!(writer, "step,x,y,direction")?;
+    let mut seen = Grid::new(grid.width(), grid.height(), 0u8);
+    let mut step = 0;
+    loop {
+        if !record(&mut seen, guard) {
+            return Err(NEVER_LEAVES.into());
+        }
+        let (row, col) = guard.position;
+        writeln!(writer, "{},{},{},{}", step, col, row, guard.direction)?;
+        if !guard.step(grid) {
+            return Ok(());
+        }
+        step += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_visited() {
+        let (grid, guard) = parse(EXAMPLE).unwrap();
+        assert_eq!(count_visited(&grid, guard).unwrap(), 41);
+    }
+
+    #[test]
+    fn example_loop_obstacles() {
+        let (grid, guard) = parse(EXAMPLE).unwrap();
+        assert!(!is_loop(&grid, guard));
+        assert_eq!(count_loop_obstacles(&grid, guard).unwrap(), 6);
+    }
+
+    /// The guard turns right four times and is back where they started
+    const LOOP: &str = ".#..\n...#\n#^..\n..#.\n";
+
+    #[test]
+    fn guard_in_a_loop() {
+        let (grid, guard) = parse(LOOP).unwrap();
+        assert!(is_loop(&grid, guard));
+        assert_eq!(count_visited(&grid, guard), Err(NEVER_LEAVES.to_string()));
+        assert!(count_loop_obstacles(&grid, guard).is_err());
+        let mut trace = Vec::new();
+        assert!(write_trace(&grid, guard, &mut trace).is_err());
+    }
+
+    #[test]
+    fn example_trace() {
+        let (grid, guard) = parse(EXAMPLE).unwrap();
+        let mut trace = Vec::new();
+        write_trace(&grid, guard, &mut trace).unwrap();
+        let trace = String::from_utf8(trace).unwrap();
+        let lines: Vec<&str> = trace.lines().collect();
+        assert_eq!(lines[0], "step,x,y,direction");
+        assert_eq!(lines[1], "0,4,6,up");
+        assert_eq!(lines[6], "5,4,1,up");
+        assert_eq!(lines[7], "6,4,1,right");
+        assert_eq!(lines.last(), Some(&"54,7,9,down"));
+    }
+}
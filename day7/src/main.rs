@@ -0,0 +1,228 @@
+//! Day 7:
+//! Every line holds an equation `target: a b c ...` with the operators missing.
+//! Operators are always evaluated left-to-right, without precedence.
+//! The answer is the sum of the targets of all equations that can be made true
+//! by inserting `+` or `*` between the numbers.
+//!
+//! Part 2: The concatenation operator `||` joins the digits of both sides, e.g. `12 || 345` is `12345`.
+//!
+//! `$ day7 --file-name <file> [--part 2]` to execute.
+//! `$ day7 --demo` runs on the example from the puzzle description instead.
+//! With `--show-expr` one choice of operators is printed for every solvable equation,
+//! e.g. `3267 = 81 + 40 * 27`, unless only the answer is printed.
+//!
+//! The search tries every operator of the part at every gap,
+//! so new operators only need a variant and an [`Operator::apply`] arm.
+//! The equations are searched in parallel, on as many threads as `--jobs` allows.
+//!
+use aoc_common::{CommonArgs, Part};
+use clap::Parser;
+use rayon::prelude::*;
+use std::fmt;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// Print one choice of operators for every solvable equation (ignored with --quiet and --output kv)
+    #[clap(long)]
+    show_expr: bool,
+}
+
+type NumType = u64;
+type ResultType = u64;
+
+/// The example from the puzzle description, used by `--demo`
+const EXAMPLE: &str = "190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let content = args.common.read_input(EXAMPLE)?;
+    let equations = parse(&content)?;
+
+    if args.show_expr && args.common.verbose() {
+        for part in args.common.parts() {
+            for equation in &equations {
+                if let Some(operators) = equation.solve(Operator::of(part)) {
+                    println!("{} = {}", equation.target, equation.expression(&operators));
+                }
+            }
+        }
+    }
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(args.common.jobs.unwrap_or(0))
+        .build()?;
+
+    args.common.solve(7, |part| {
+        Ok(pool.install(|| sum_solvable(&equations, Operator::of(part))))
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operator {
+    Add,
+    Multiply,
+    Concat,
+}
+
+impl Operator {
+    /// The operators of part 1 and part 2
+    const ADD_MULTIPLY: [Operator; 2] = [Operator::Add, Operator::Multiply];
+    const ALL: [Operator; 3] = [Operator::Add, Operator::Multiply, Operator::Concat];
+
+    fn of(part: Part) -> &'static [Operator] {
+        match part {
+            Part::One => &Operator::ADD_MULTIPLY,
+            Part::Two => &Operator::ALL,
+        }
+    }
+
+    /// Applies the operator, or returns `None` if the result overflows
+    fn apply(self, left: NumType, right: NumType) -> Option<NumType> {
+        match self {
+            Operator::Add => left.checked_add(right),
+            Operator::Multiply => left.checked_mul(right),
+            Operator::Concat => {
+                let shift = (10 as NumType).checked_pow(right.checked_ilog10().unwrap_or(0) + 1)?;
+                left.checked_mul(shift)?.checked_add(right)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operator::Add => write!(f, "+"),
+            Operator::Multiply => write!(f, "*"),
+            Operator::Concat => write!(f, "||"),
+        }
+    }
+}
+
+struct Equation {
+    target: NumType,
+    numbers: Vec<NumType>,
+}
+
+impl Equation {
+    /// Checks if some choice of operators makes the numbers evaluate to the target
+    fn is_solvable(&self, operators: &[Operator]) -> bool {
+        self.solve(operators).is_some()
+    }
+
+    /// Finds a choice of operators, one for every gap, that makes the numbers evaluate to the target
+    fn solve(&self, operators: &[Operator]) -> Option<Vec<Operator>> {
+        let (first, rest) = self.numbers.split_first()?;
+        let mut chosen = search(self.target, *first, rest, operators)?;
+        chosen.reverse();
+        Some(chosen)
+    }
+
+    /// Writes the numbers with the operators in the gaps, e.g. `81 + 40 * 27`
+    fn expression(&self, operators: &[Operator]) -> String {
+        let mut expression = self
+            .numbers
+            .first()
+            .map_or(String::new(), |n| n.to_string());
+        for (operator, number) in operators.iter().zip(&self.numbers[1..]) {
+            expression.push_str(&format!(" {} {}", operator, number));
+        }
+        expression
+    }
+}
+
+/// Depth-first search over the operators for the remaining numbers,
+/// with `acc` being the value of everything to the left.
+/// The chosen operators are returned last gap first, as they are collected while unwinding.
+///
+/// No operator makes `acc` smaller unless a number is 0, so once `acc` exceeds the target
+/// the branch is given up, as long as no 0 follows.
+fn search(
+    target: NumType,
+    acc: NumType,
+    rest: &[NumType],
+    operators: &[Operator],
+) -> Option<Vec<Operator>> {
+    if acc > target && !rest.contains(&0) {
+        return None;
+    }
+    match rest.split_first() {
+        None => (acc == target).then(Vec::new),
+        Some((next, rest)) => operators.iter().find_map(|operator| {
+            let acc = operator.apply(acc, *next)?;
+            let mut chosen = search(target, acc, rest, operators)?;
+            chosen.push(*operator);
+            Some(chosen)
+        }),
+    }
+}
+
+fn parse(content: &str) -> Result<Vec<Equation>, Box<dyn std::error::Error>> {
+    let mut equations = Vec::new();
+    for line in content.lines().filter(|line| !line.trim().is_empty()) {
+        let (target, numbers) = line
+            .split_once(':')
+            .ok_or_else(|| format!("Missing ':' in {}", line))?;
+        equations.push(Equation {
+            target: target.trim().parse()?,
+            numbers: numbers
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<Result<_, _>>()?,
+        });
+    }
+    Ok(equations)
+}
+
+fn sum_solvable(equations: &[Equation], operators: &[Operator]) -> ResultType {
+    equations
+        .par_iter()
+        .filter(|equation| equation.is_solvable(operators))
+        .map(|equation| equation.target)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_add_multiply() {
+        assert_eq!(
+            sum_solvable(&parse(EXAMPLE).unwrap(), &Operator::ADD_MULTIPLY),
+            3749
+        );
+    }
+
+    #[test]
+    fn example_expression() {
+        let equations = parse(EXAMPLE).unwrap();
+        let operators = equations[1].solve(&Operator::ADD_MULTIPLY).unwrap();
+        assert_eq!(equations[1].expression(&operators), "81 + 40 * 27");
+        assert_eq!(equations[2].solve(&Operator::ADD_MULTIPLY), None);
+    }
+
+    #[test]
+    fn example_concat() {
+        let equations = parse(EXAMPLE).unwrap();
+        assert_eq!(Operator::Concat.apply(12, 345), Some(12345));
+        assert_eq!(Operator::Concat.apply(12, 0), Some(120));
+        let operators = equations[4].solve(&Operator::ALL).unwrap();
+        assert_eq!(equations[4].expression(&operators), "6 * 8 || 6 * 15");
+        assert_eq!(sum_solvable(&equations, &Operator::ALL), 11387);
+    }
+}
@@ -0,0 +1,214 @@
+//! Day 8:
+//! The map shows antennas, each marked by a letter or digit for its frequency; `.` is empty.
+//! Every pair of antennas with the same frequency creates two antinodes:
+//! one on each side of the pair, as far away from the nearer antenna as the antennas are apart.
+//! The answer is the number of distinct positions inside the map that hold an antinode.
+//!
+//! Part 2: Resonant harmonics create antinodes at every multiple of the distance between a pair,
+//! along the whole line through both antennas, including the antennas themselves.
+//! `--max-harmonics N` only projects up to `N` times the distance from each antenna.
+//!
+//! `$ day8 --file-name <file> [--part 2 [--max-harmonics <n>]]` to execute.
+//! `$ day8 --demo` runs on the example from the puzzle description instead.
+//!
+//! Counting can be restricted to a part of the map:
+//! - `--bounds WxH` only counts the `W` columns and `H` rows in the top-left corner.
+//! - `--crop x0,y0,x1,y1` only counts between the corners `(x0, y0)` and `(x1, y1)`, both included.
+//!
+//! `x` is the column and `y` the row, starting at 0. Antinodes outside the map are never counted.
+//!
+use aoc_common::grid::{Bounds, Grid, Position};
+use aoc_common::{CommonArgs, Part};
+use clap::Parser;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// Only count antinodes in the top-left `WxH` rectangle
+    #[clap(long, value_parser = parse_size, conflicts_with = "crop")]
+    bounds: Option<Bounds>,
+
+    /// Only count antinodes between the corners `x0,y0,x1,y1` (inclusive)
+    #[clap(long, value_parser = parse_crop)]
+    crop: Option<Bounds>,
+
+    /// Only project part 2 antinodes up to this many times the distance of a pair
+    #[clap(long)]
+    max_harmonics: Option<usize>,
+}
+
+type ResultType = usize;
+
+/// The example from the puzzle description, used by `--demo`
+const EXAMPLE: &str = "............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let content = args.common.read_input(EXAMPLE)?;
+    let map = Map::parse(&content)?;
+    let bounds = match args.bounds.or(args.crop) {
+        Some(region) => map.bounds.intersect(&region),
+        None => map.bounds,
+    };
+
+    args.common.solve(8, |part| match part {
+        Part::One => Ok(count_antinodes(&map, &bounds)),
+        Part::Two => Ok(count_resonant_antinodes(&map, &bounds, args.max_harmonics)),
+    })
+}
+
+/// Parses `WxH` into bounds starting at the top-left corner
+fn parse_size(s: &str) -> Result<Bounds, String> {
+    let (width, height) = s
+        .split_once('x')
+        .ok_or_else(|| format!("Expected WxH, got {}", s))?;
+    let width: isize = width.parse().map_err(|e| format!("Invalid width: {}", e))?;
+    let height: isize = height
+        .parse()
+        .map_err(|e| format!("Invalid height: {}", e))?;
+    if width < 0 || height < 0 {
+        return Err(format!("The size must not be negative, got {}", s));
+    }
+    Ok(Bounds::new(width, height))
+}
+
+/// Parses `x0,y0,x1,y1` into bounds including both corners
+fn parse_crop(s: &str) -> Result<Bounds, String> {
+    let numbers = s
+        .split(',')
+        .map(|n| n.trim().parse::<isize>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Invalid coordinate: {}", e))?;
+    match numbers[..] {
+        [x0, y0, x1, y1] => {
+            let after = |a: isize, b: isize| {
+                a.max(b)
+                    .checked_add(1)
+                    .ok_or_else(|| format!("Coordinate too large in {}", s))
+            };
+            Ok(Bounds {
+                start: (y0.min(y1), x0.min(x1)),
+                end: (after(y0, y1)?, after(x0, x1)?),
+            })
+        }
+        _ => Err(format!("Expected x0,y0,x1,y1, got {}", s)),
+    }
+}
+
+/// The size of the map and the antennas on it, grouped by frequency
+struct Map {
+    bounds: Bounds,
+    antennas: HashMap<char, Vec<Position>>,
+}
+
+impl Map {
+    fn parse(content: &str) -> Result<Self, String> {
+        let grid = Grid::parse(content, |_, c| Some(c))?;
+
+        let mut antennas: HashMap<char, Vec<Position>> = HashMap::new();
+        for position in grid.positions() {
+            if grid[position] != '.' {
+                antennas.entry(grid[position]).or_default().push(position);
+            }
+        }
+
+        Ok(Map {
+            bounds: grid.bounds(),
+            antennas,
+        })
+    }
+}
+
+/// Collects the antinodes of every pair of antennas with the same frequency that lie within the bounds
+fn count_antinodes(map: &Map, bounds: &Bounds) -> ResultType {
+    let mut antinodes = HashSet::new();
+    for positions in map.antennas.values() {
+        for (i, a) in positions.iter().enumerate() {
+            for b in &positions[i + 1..] {
+                let (d_row, d_col) = (b.0 - a.0, b.1 - a.1);
+                for antinode in [(a.0 - d_row, a.1 - d_col), (b.0 + d_row, b.1 + d_col)] {
+                    if bounds.contains(antinode) {
+                        antinodes.insert(antinode);
+                    }
+                }
+            }
+        }
+    }
+    antinodes.len()
+}
+
+/// Like [`count_antinodes`], but projects the antinodes along the whole line through each pair,
+/// starting at the antennas themselves, until the map ends or `max_harmonics` is reached
+fn count_resonant_antinodes(
+    map: &Map,
+    bounds: &Bounds,
+    max_harmonics: Option<usize>,
+) -> ResultType {
+    let mut antinodes = HashSet::new();
+    for positions in map.antennas.values() {
+        for (i, a) in positions.iter().enumerate() {
+            for b in &positions[i + 1..] {
+                let (d_row, d_col) = (b.0 - a.0, b.1 - a.1);
+                for (start, (d_row, d_col)) in [(a, (-d_row, -d_col)), (b, (d_row, d_col))] {
+                    let line = (0..)
+                        .map(|k| (start.0 + k * d_row, start.1 + k * d_col))
+                        .take_while(|antinode| map.bounds.contains(*antinode))
+                        .take(max_harmonics.map_or(usize::MAX, |max| max.saturating_add(1)));
+                    antinodes.extend(line.filter(|antinode| bounds.contains(*antinode)));
+                }
+            }
+        }
+    }
+    antinodes.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_antinodes() {
+        let map = Map::parse(EXAMPLE).unwrap();
+        assert_eq!(count_antinodes(&map, &map.bounds), 14);
+    }
+
+    #[test]
+    fn example_resonant_antinodes() {
+        let map = Map::parse(EXAMPLE).unwrap();
+        assert_eq!(count_resonant_antinodes(&map, &map.bounds, None), 34);
+        // The part 1 antinodes and the 7 antennas, one of which already holds an antinode
+        assert_eq!(count_resonant_antinodes(&map, &map.bounds, Some(1)), 20);
+    }
+
+    #[test]
+    fn example_antinodes_cropped() {
+        let map = Map::parse(EXAMPLE).unwrap();
+        let bounds = map.bounds.intersect(&parse_crop("0,0,5,5").unwrap());
+        assert_eq!(count_antinodes(&map, &bounds), 4);
+    }
+
+    #[test]
+    fn invalid_regions() {
+        assert!(parse_size("-1x5").is_err());
+        assert!(parse_size("3x-2").is_err());
+        assert_eq!(parse_size("3x2"), Ok(Bounds::new(3, 2)));
+        assert!(parse_crop(&format!("0,0,{},1", isize::MAX)).is_err());
+    }
+}
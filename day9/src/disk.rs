@@ -0,0 +1,49 @@
+//! The block model of the disk, shared by all compaction strategies.
+
+/// Files are numbered in the order they appear in the disk map, starting at 0
+pub type FileId = usize;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Block {
+    Free,
+    File(FileId),
+}
+
+/// The disk as a list of single blocks
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Disk {
+    pub blocks: Vec<Block>,
+}
+
+impl Disk {
+    /// Expands the dense disk map.
+    /// Its digits alternate between the length of a file and the length of the free space after it.
+    pub fn parse(dense: &str) -> Result<Self, String> {
+        let mut blocks = Vec::new();
+        for (i, c) in dense.trim().chars().enumerate() {
+            let length = c
+                .to_digit(10)
+                .ok_or_else(|| format!("Unexpected character {:?} in the disk map", c))?;
+            let block = if i % 2 == 0 {
+                Block::File(i / 2)
+            } else {
+                Block::Free
+            };
+            blocks.extend(std::iter::repeat_n(block, length as usize));
+        }
+        Ok(Disk { blocks })
+    }
+
+    /// The sum of every block's position multiplied with the id of the file in it.
+    /// Free blocks do not count.
+    pub fn checksum(&self) -> u64 {
+        self.blocks
+            .iter()
+            .enumerate()
+            .map(|(position, block)| match block {
+                Block::File(id) => (position * id) as u64,
+                Block::Free => 0,
+            })
+            .sum()
+    }
+}
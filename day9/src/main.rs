@@ -0,0 +1,158 @@
+//! Day 9:
+//! The input is a dense disk map: its digits alternate between the length of a file
+//! and the length of the free space that follows it. Files are numbered from 0 in order.
+//!
+//! Compaction moves single blocks from the end of the disk into the leftmost free block,
+//! until there are no gaps left between the files.
+//! The answer is the checksum of the compacted disk (see [`disk::Disk::checksum`]).
+//!
+//! Part 2: Whole files are moved instead, each once, starting with the highest file id.
+//! A file moves into the leftmost free span left of it that is large enough, or stays.
+//!
+//! `$ day9 --file-name <file> [--part 2]` to execute.
+//! `$ day9 --demo` runs on the example from the puzzle description instead.
+//!
+mod disk;
+
+use aoc_common::{CommonArgs, Part};
+use clap::Parser;
+use disk::{Block, Disk, FileId};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
+}
+
+/// The example from the puzzle description, used by `--demo`
+const EXAMPLE: &str = "2333133121414131402";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let content = args.common.read_input(EXAMPLE)?;
+    let disk = Disk::parse(&content)?;
+
+    args.common.solve(9, |part| match part {
+        Part::One => {
+            let mut disk = disk.clone();
+            compact_blocks(&mut disk);
+            Ok(disk.checksum())
+        }
+        Part::Two => {
+            let mut disk = disk.clone();
+            compact_files(&mut disk);
+            Ok(disk.checksum())
+        }
+    })
+}
+
+/// Moves the last file block into the first free block until all free blocks are at the end
+fn compact_blocks(disk: &mut Disk) {
+    let blocks = &mut disk.blocks;
+    let mut free = 0;
+    let mut last = blocks.len();
+    loop {
+        while free < last && blocks[free] != Block::Free {
+            free += 1;
+        }
+        while last > free && blocks[last - 1] == Block::Free {
+            last -= 1;
+        }
+        if free + 1 >= last {
+            break;
+        }
+        blocks.swap(free, last - 1);
+    }
+}
+
+/// A run of blocks as `(start, length)`
+type Span = (usize, usize);
+
+/// The free spans of the disk, grouped by their length.
+/// Each group is a min-heap of start positions, so the leftmost span of a length is on top.
+struct FreeSpans {
+    by_length: Vec<BinaryHeap<Reverse<usize>>>,
+}
+
+impl FreeSpans {
+    fn new(spans: &[Span]) -> Self {
+        let longest = spans.iter().map(|(_, length)| *length).max().unwrap_or(0);
+        let mut by_length = vec![BinaryHeap::new(); longest + 1];
+        for (start, length) in spans {
+            by_length[*length].push(Reverse(*start));
+        }
+        FreeSpans { by_length }
+    }
+
+    /// Takes `length` blocks from the leftmost span that is long enough and starts before `before`.
+    /// The rest of that span stays free. Returns where the taken blocks start.
+    fn take(&mut self, length: usize, before: usize) -> Option<usize> {
+        let (span_length, Reverse(start)) = self
+            .by_length
+            .iter()
+            .enumerate()
+            .skip(length)
+            .filter_map(|(span_length, heap)| Some((span_length, *heap.peek()?)))
+            .filter(|(_, Reverse(start))| *start < before)
+            .min_by_key(|(_, Reverse(start))| *start)?;
+        self.by_length[span_length].pop();
+        if span_length > length {
+            self.by_length[span_length - length].push(Reverse(start + length));
+        }
+        Some(start)
+    }
+}
+
+/// The spans of all files with their ids, in the order of the disk, and all free spans
+fn spans(disk: &Disk) -> (Vec<(FileId, Span)>, Vec<Span>) {
+    let mut files: Vec<(FileId, Span)> = Vec::new();
+    let mut free: Vec<Span> = Vec::new();
+    let mut previous = None;
+    for (position, block) in disk.blocks.iter().enumerate() {
+        let continues = previous == Some(*block);
+        match (block, files.last_mut(), free.last_mut()) {
+            (Block::File(_), Some((_, (_, length))), _) if continues => *length += 1,
+            (Block::File(id), _, _) => files.push((*id, (position, 1))),
+            (Block::Free, _, Some((_, length))) if continues => *length += 1,
+            (Block::Free, _, _) => free.push((position, 1)),
+        }
+        previous = Some(*block);
+    }
+    (files, free)
+}
+
+/// Moves every file once, highest id first, into the leftmost free span left of it that fits.
+/// Space freed by a move is never used again: every file that is still to be moved lies further left.
+fn compact_files(disk: &mut Disk) {
+    let (files, free) = spans(disk);
+    let mut free = FreeSpans::new(&free);
+    for (id, (start, length)) in files.iter().rev() {
+        if let Some(target) = free.take(*length, *start) {
+            disk.blocks[*start..start + length].fill(Block::Free);
+            disk.blocks[target..target + length].fill(Block::File(*id));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_block_compaction() {
+        let mut disk = Disk::parse(EXAMPLE).unwrap();
+        compact_blocks(&mut disk);
+        assert_eq!(disk.checksum(), 1928);
+    }
+
+    #[test]
+    fn example_file_compaction() {
+        let mut disk = Disk::parse(EXAMPLE).unwrap();
+        compact_files(&mut disk);
+        assert_eq!(disk.checksum(), 2858);
+    }
+}
@@ -0,0 +1,100 @@
+//! Iterator adapters shared by the days.
+//!
+//! - [`Toggle`] (via [`Toggleable`]) discards chunks of elements between an "off" and an "on" element.
+//! - [`Pairwise`] (via [`IterUtils::pairwise`]) yields every element together with the one after it.
+//! - [`WindowsTuple`] (via [`IterUtils::windows_tuple`]) yields every run of `N` consecutive elements as an array.
+//! - [`Sliding`] (via [`IterUtils::sliding_max`] and [`IterUtils::sliding_min`]) yields
+//!   the largest or smallest element of every window of a fixed size.
+//!
+mod sliding;
+mod toggle;
+mod windows;
+
+pub use sliding::Sliding;
+pub use toggle::{Toggle, Toggleable};
+pub use windows::{Pairwise, WindowsTuple};
+
+/// Extension methods for all iterators
+pub trait IterUtils: Iterator + Sized {
+    /// Yields `(a, b)` for every element `a` and the element `b` right after it.
+    /// `[1, 2, 3]` becomes `(1, 2), (2, 3)`.
+    fn pairwise(self) -> Pairwise<Self>
+    where
+        Self::Item: Clone,
+    {
+        Pairwise::new(self)
+    }
+
+    /// Yields every run of `N` consecutive elements, like [`slice::windows`] but as arrays.
+    /// `[1, 2, 3, 4]` with `N = 3` becomes `[1, 2, 3], [2, 3, 4]`.
+    ///
+    /// # Panics
+    /// If `N` is 0.
+    fn windows_tuple<const N: usize>(self) -> WindowsTuple<Self, N>
+    where
+        Self::Item: Clone,
+    {
+        WindowsTuple::new(self)
+    }
+
+    /// Yields the largest element of every window of `size` consecutive elements.
+    ///
+    /// # Panics
+    /// If `size` is 0.
+    fn sliding_max(self, size: usize) -> Sliding<Self>
+    where
+        Self::Item: Ord + Clone,
+    {
+        Sliding::new(self, size, true)
+    }
+
+    /// Yields the smallest element of every window of `size` consecutive elements.
+    ///
+    /// # Panics
+    /// If `size` is 0.
+    fn sliding_min(self, size: usize) -> Sliding<Self>
+    where
+        Self::Item: Ord + Clone,
+    {
+        Sliding::new(self, size, false)
+    }
+}
+
+impl<I: Iterator> IterUtils for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pairwise() {
+        let pairs: Vec<_> = [1, 2, 3].into_iter().pairwise().collect();
+        assert_eq!(pairs, vec![(1, 2), (2, 3)]);
+        assert_eq!([1].into_iter().pairwise().next(), None);
+    }
+
+    #[test]
+    fn windows_tuple() {
+        let windows: Vec<_> = [1, 2, 3, 4].into_iter().windows_tuple::<3>().collect();
+        assert_eq!(windows, vec![[1, 2, 3], [2, 3, 4]]);
+        assert_eq!([1, 2].into_iter().windows_tuple::<3>().next(), None);
+    }
+
+    #[test]
+    fn sliding_max_and_min() {
+        let numbers = [1, 3, -1, -3, 5, 3, 6, 7];
+        let maxima: Vec<_> = numbers.into_iter().sliding_max(3).collect();
+        assert_eq!(maxima, vec![3, 3, 5, 5, 6, 7]);
+        let minima: Vec<_> = numbers.into_iter().sliding_min(3).collect();
+        assert_eq!(minima, vec![-1, -3, -3, -3, 3, 3]);
+    }
+
+    #[test]
+    fn toggle() {
+        let kept: Vec<_> = [1, 0, 2, 9, 3]
+            .into_iter()
+            .toggle_on(|x| *x == 9, |x| *x == 0)
+            .collect();
+        assert_eq!(kept, vec![1, 9, 3]);
+    }
+}
@@ -0,0 +1,69 @@
+use std::collections::VecDeque;
+
+/// An iterator over the largest or smallest element of every window,
+/// see [`crate::IterUtils::sliding_max`] and [`crate::IterUtils::sliding_min`].
+///
+/// It keeps a monotonic queue of the elements that can still become the extreme of a window,
+/// so every element is added and removed at most once.
+pub struct Sliding<I: Iterator> {
+    iter: I,
+    size: usize,
+    maximum: bool,
+    index: usize,
+    /// Candidates with their index, the extreme of the current window at the front
+    candidates: VecDeque<(usize, I::Item)>,
+}
+
+impl<I: Iterator> Sliding<I> {
+    /// Yields the maximum of every window if `maximum` is set, the minimum otherwise
+    pub fn new(iter: I, size: usize, maximum: bool) -> Self {
+        assert!(size > 0, "windows need at least one element");
+        Sliding {
+            iter,
+            size,
+            maximum,
+            index: 0,
+            candidates: VecDeque::with_capacity(size),
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for Sliding<I>
+where
+    I::Item: Ord + Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let item = self.iter.next()?;
+            let index = self.index;
+            self.index += 1;
+
+            // A candidate that is beaten by a newer element can never be the extreme again
+            while let Some((_, last)) = self.candidates.back() {
+                let beaten = if self.maximum {
+                    *last <= item
+                } else {
+                    *last >= item
+                };
+                if !beaten {
+                    break;
+                }
+                self.candidates.pop_back();
+            }
+            self.candidates.push_back((index, item));
+
+            while let Some((first, _)) = self.candidates.front() {
+                if first + self.size > index {
+                    break;
+                }
+                self.candidates.pop_front();
+            }
+
+            if index + 1 >= self.size {
+                return self.candidates.front().map(|(_, item)| item.clone());
+            }
+        }
+    }
+}
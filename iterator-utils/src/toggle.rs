@@ -0,0 +1,93 @@
+pub struct Toggle<J, I: Iterator<Item = J>, POn: FnMut(&J) -> bool, POff: FnMut(&J) -> bool> {
+    iter: I,
+    on_function: POn,
+    off_function: POff,
+    state: bool,
+}
+
+impl<J, I: Iterator<Item = J>, POn: FnMut(&J) -> bool, POff: FnMut(&J) -> bool>
+    Toggle<J, I, POn, POff>
+{
+    pub fn new(iter: I, on_function: POn, off_function: POff, initial_state: bool) -> Self {
+        Toggle {
+            iter,
+            on_function,
+            off_function,
+            state: initial_state,
+        }
+    }
+
+    pub fn new_on(iter: I, on_function: POn, off_function: POff) -> Self {
+        Self::new(iter, on_function, off_function, true)
+    }
+
+    pub fn new_off(iter: I, on_function: POn, off_function: POff) -> Self {
+        Self::new(iter, on_function, off_function, false)
+    }
+}
+
+/// An Iterator that lets you discard large chunks of data according to some toggle-rules.
+/// When getting the next element, this iterator gets one from the underlying iterator,
+/// then checks if the new element changes its state.
+/// After that, if the current state is on, the element is returned.
+/// If the state is on, the procedure is repeated until an element is found that turns its state on.
+///
+/// The element that turns a state on will be returned.
+/// The element that turns a state off will not be returned.
+///
+/// While the state is on, the on-function will not be queried.
+/// While the state is off, the off-function will not be queried.
+impl<J, I: Iterator<Item = J>, POn: FnMut(&J) -> bool, POff: FnMut(&J) -> bool> Iterator
+    for Toggle<J, I, POn, POff>
+{
+    type Item = J;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for v in self.iter.by_ref() {
+            if self.state {
+                if (self.off_function)(&v) {
+                    self.state = false;
+                }
+            } else {
+                if (self.on_function)(&v) {
+                    self.state = true;
+                }
+            }
+            if self.state {
+                return Some(v);
+            }
+        }
+        None
+    }
+}
+pub trait Toggleable<J, I: Iterator<Item = J>, POn: FnMut(&J) -> bool, POff: FnMut(&J) -> bool>:
+    Iterator<Item = J> + Sized
+{
+    fn toggle_on(self, on_function: POn, off_function: POff) -> Toggle<J, I, POn, POff>;
+    fn toggle_off(self, on_function: POn, off_function: POff) -> Toggle<J, I, POn, POff>;
+    fn toggle(
+        self,
+        on_function: POn,
+        off_function: POff,
+        initial_state: bool,
+    ) -> Toggle<J, I, POn, POff>;
+}
+
+impl<J, I: Iterator<Item = J>, POn: FnMut(&J) -> bool, POff: FnMut(&J) -> bool>
+    Toggleable<J, Self, POn, POff> for I
+{
+    fn toggle_on(self, on_function: POn, off_function: POff) -> Toggle<J, I, POn, POff> {
+        Toggle::new_on(self, on_function, off_function)
+    }
+    fn toggle_off(self, on_function: POn, off_function: POff) -> Toggle<J, I, POn, POff> {
+        Toggle::new_off(self, on_function, off_function)
+    }
+    fn toggle(
+        self,
+        on_function: POn,
+        off_function: POff,
+        initial_state: bool,
+    ) -> Toggle<J, I, POn, POff> {
+        Toggle::new(self, on_function, off_function, initial_state)
+    }
+}
@@ -0,0 +1,60 @@
+/// An iterator over adjacent pairs, see [`crate::IterUtils::pairwise`]
+pub struct Pairwise<I: Iterator> {
+    iter: I,
+    last: Option<I::Item>,
+}
+
+impl<I: Iterator> Pairwise<I> {
+    pub fn new(iter: I) -> Self {
+        Pairwise { iter, last: None }
+    }
+}
+
+impl<I: Iterator> Iterator for Pairwise<I>
+where
+    I::Item: Clone,
+{
+    type Item = (I::Item, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.last.is_none() {
+            self.last = self.iter.next();
+        }
+        let next = self.iter.next()?;
+        let last = self.last.replace(next.clone())?;
+        Some((last, next))
+    }
+}
+
+/// An iterator over runs of `N` consecutive elements, see [`crate::IterUtils::windows_tuple`]
+pub struct WindowsTuple<I: Iterator, const N: usize> {
+    iter: I,
+    window: Vec<I::Item>,
+}
+
+impl<I: Iterator, const N: usize> WindowsTuple<I, N> {
+    pub fn new(iter: I) -> Self {
+        assert!(N > 0, "windows need at least one element");
+        WindowsTuple {
+            iter,
+            window: Vec::with_capacity(N),
+        }
+    }
+}
+
+impl<I: Iterator, const N: usize> Iterator for WindowsTuple<I, N>
+where
+    I::Item: Clone,
+{
+    type Item = [I::Item; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.window.len() == N {
+            self.window.remove(0);
+        }
+        while self.window.len() < N {
+            self.window.push(self.iter.next()?);
+        }
+        Some(std::array::from_fn(|i| self.window[i].clone()))
+    }
+}